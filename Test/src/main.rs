@@ -1,4 +1,4 @@
-use std::io::{self, BufRead, Write};
+use std::io::{self, Write};
 
 fn main() -> io::Result<()> {
     let mut line = String::new();
@@ -7,7 +7,7 @@ fn main() -> io::Result<()> {
         if n == 0 {
             break;
         }
-        io::stdout().write(line.as_bytes())?;
+        io::stdout().write_all(line.as_bytes())?;
     }
     Ok(())
 }
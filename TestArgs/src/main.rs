@@ -1,6 +1,6 @@
 fn main() {
     for arg in std::env::args() {
-        print!("{} {}", arg);
+        print!("{} ", arg);
     }
-    println!("");
+    println!();
 }
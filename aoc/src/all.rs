@@ -0,0 +1,218 @@
+//! `aoc all`: every day against its puzzle input, solved on a pool of
+//! threads and checked against the answers in its manifest.
+//!
+//! A day's puzzle input is `input.txt` in its directory. Days that take
+//! values on the command line find them in the file instead, separated by
+//! whitespace. Days without an `input.txt` run on the manifest input that
+//! checks the most parts instead, and are only skipped if they have no
+//! manifest entries either. Parameters come from `input.params` if there
+//! is one.
+
+use std::any::Any;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Input, InputShape, Params};
+
+use crate::manifest::{self, Entry};
+use crate::{Day, PARTS};
+
+/// How a day's answers compare with its manifest.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    /// Every part gave its expected answer.
+    Ok,
+    /// Some parts have no expected answer for the puzzle input; the rest
+    /// matched.
+    Unchecked,
+    /// The parts that gave a different answer.
+    Mismatch(Vec<u32>),
+    /// The day has no `input.txt` and nothing in its manifest to run.
+    NoInput,
+    /// The day couldn't be run at all.
+    Error(String),
+}
+
+/// One day's row of the summary.
+pub struct Outcome {
+    pub day: u32,
+    /// The answer to each of `PARTS`, if it was solved.
+    pub answers: Vec<Option<String>>,
+    /// Parsing and both parts together, if the day was run.
+    pub elapsed: Option<Duration>,
+    pub status: Status,
+}
+
+const INPUT_FILE : &str = "input.txt";
+
+/// The arguments a day would be given for its puzzle input, as they'd be
+/// written in its manifest `entries`. Without an `input.txt`, these are the
+/// arguments of the manifest input with answers for the most parts, the
+/// last of them if there's a tie.
+pub fn puzzle_args(day: &Day, dir: &Path, entries: &[Entry]) -> io::Result<Vec<String>> {
+    match read_puzzle_args(day, dir) {
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+            let parts = |args: &Vec<String>| entries.iter().filter(|entry| entry.args == *args).count();
+            entries.iter().map(|entry| &entry.args)
+                .fold(None, |best: Option<&Vec<String>>, args| match best {
+                    Some(best) if parts(best) > parts(args) => Some(best),
+                    _ => Some(args)
+                })
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no puzzle input"))
+        },
+        result => result
+    }
+}
+
+fn read_puzzle_args(day: &Day, dir: &Path) -> io::Result<Vec<String>> {
+    match day.solver.input_shape() {
+        InputShape::File => {
+            fs::metadata(dir.join(INPUT_FILE))?;
+            Ok(vec![INPUT_FILE.to_string()])
+        },
+        InputShape::Values(_) => {
+            let text = fs::read_to_string(dir.join(INPUT_FILE))?;
+            Ok(text.split_whitespace().map(|value| value.to_string()).collect())
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("panicked", |s| s).to_string()
+    }
+}
+
+/// Solves both parts of `day` for its puzzle input. A solver that panics
+/// is reported as an error rather than taking the other days down with it.
+pub fn run_day(day: &Day) -> Outcome {
+    let mut outcome = Outcome {
+        day: day.number,
+        answers: vec![None; PARTS.len()],
+        elapsed: None,
+        status: Status::Ok
+    };
+
+    let dir = manifest::day_dir(day.number);
+    let entries = match manifest::load(&dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            outcome.status = Status::Error(format!("can't read {}: {}", manifest::FILE_NAME, e));
+            return outcome;
+        }
+    };
+    let args = match puzzle_args(day, &dir, &entries) {
+        Ok(args) => args,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+            outcome.status = Status::NoInput;
+            return outcome;
+        },
+        Err(e) => {
+            outcome.status = Status::Error(e.to_string());
+            return outcome;
+        }
+    };
+
+    let (input, params) = match manifest::load_input(day, &dir, &args) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            outcome.status = Status::Error(e.to_string());
+            return outcome;
+        }
+    };
+    check_input(day, &input, &params, &entries, &args)
+}
+
+/// Solves both parts of `day` for `input` with `params`, and compares the
+/// answers with the entries in `entries` for `args`, the arguments the
+/// input was loaded from. A solver that panics is reported as an error.
+pub fn check_input(day: &Day, input: &Input, params: &Params, entries: &[Entry], args: &[String]) -> Outcome {
+    let mut outcome = Outcome {
+        day: day.number,
+        answers: vec![None; PARTS.len()],
+        elapsed: None,
+        status: Status::Ok
+    };
+
+    let start = Instant::now();
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| day.solver.parse_input_with(&input.text, params)));
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => {
+            outcome.status = Status::Error(format!("{}:{}:{}: {}", input.name, e.line, e.col, e.message));
+            return outcome;
+        },
+        Err(payload) => {
+            outcome.status = Status::Error(panic_message(payload));
+            return outcome;
+        }
+    };
+
+    let mut mismatched = Vec::new();
+    let mut unchecked = false;
+    for (index, &part) in PARTS.iter().enumerate() {
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| day.solver.answer(&*parsed, part).to_string()));
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(payload) => {
+                outcome.status = Status::Error(format!("part {}: {}", part, panic_message(payload)));
+                outcome.elapsed = Some(start.elapsed());
+                return outcome;
+            }
+        };
+        match entries.iter().find(|entry| entry.part == part && entry.args == args) {
+            Some(entry) if entry.expected != answer => mismatched.push(part),
+            Some(_) => {},
+            None => unchecked = true
+        }
+        outcome.answers[index] = Some(answer);
+    }
+    outcome.elapsed = Some(start.elapsed());
+
+    outcome.status = if !mismatched.is_empty() {
+        Status::Mismatch(mismatched)
+    }
+    else if unchecked {
+        Status::Unchecked
+    }
+    else {
+        Status::Ok
+    };
+    outcome
+}
+
+/// Runs every day in `days` on `threads` threads, returning the outcomes
+/// in the order of `days`.
+pub fn run_all(days: &[Day], threads: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..std::cmp::max(1, threads) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= days.len() {
+                        break;
+                    }
+                    let _ = sender.send((index, run_day(&days[index])));
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut outcomes : Vec<(usize, Outcome)> = receiver.into_iter().collect();
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
@@ -0,0 +1,62 @@
+//! `aoc batch`: one day over many input files, such as all of a day's
+//! examples. With `--check`, each file's answers are compared with the
+//! lines naming it in the `answers.txt` in its directory. Manifests and
+//! `.params` files among the files given are left out, so that a glob
+//! over a day's directory only picks up its inputs.
+
+use std::io;
+use std::path::Path;
+
+use aoc_common::{params, Params};
+
+use crate::all::{self, Outcome, Status};
+use crate::manifest;
+use crate::{Day, PARTS};
+
+/// The inputs among `paths`: everything but manifests and parameter files.
+pub fn inputs(paths: &[String]) -> Vec<&String> {
+    paths.iter().filter(|path| {
+        let path = Path::new(path);
+        path.file_name() != Some(manifest::FILE_NAME.as_ref())
+            && path.extension() != Some(params::EXTENSION.as_ref())
+    }).collect()
+}
+
+/// Solves both parts of `day` for the file at `path`, with the parameters
+/// in its `.params` file and then `set`. If `check` is set and nothing
+/// was, the answers are compared with the manifest next to the file.
+pub fn run_file(day: &Day, path: &Path, set: &Params, check: bool) -> Outcome {
+    let failed = |message: String| Outcome {
+        day: day.number,
+        answers: vec![None; PARTS.len()],
+        elapsed: None,
+        status: Status::Error(message)
+    };
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let args = match path.file_name() {
+        Some(name) => vec![name.to_string_lossy().into_owned()],
+        None => return failed(format!("{} is not a file", path.display()))
+    };
+    let (input, mut params) = match manifest::load_input(day, dir, &args) {
+        Ok(loaded) => loaded,
+        Err(e) => return failed(e.to_string())
+    };
+    params.extend(set);
+    if let Err(e) = params.check(day.number, day.solver.params()) {
+        return failed(e);
+    }
+
+    // The manifest's answers are for the parameters the file comes with
+    let entries = if check && set.is_empty() {
+        match manifest::load(dir) {
+            Ok(entries) => entries,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return failed(format!("can't read {}: {}", manifest::FILE_NAME, e))
+        }
+    }
+    else {
+        Vec::new()
+    };
+    all::check_input(day, &input, &params, &entries, &args)
+}
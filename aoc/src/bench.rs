@@ -0,0 +1,156 @@
+//! Timing for `aoc run --bench`: repeated runs of each stage of a day, and
+//! a baseline file to compare later runs against.
+//!
+//! A baseline file has one `<day> <stage> <median nanoseconds>` per line,
+//! where the stage is `parse`, `part1` or `part2`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How much slower than its baseline a stage may get before it counts as
+/// a regression. Generous, since medians of short runs are noisy.
+pub const REGRESSION_THRESHOLD : f64 = 1.25;
+
+/// One step of solving a day.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Stage {
+    Parse,
+    Part(u32),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => "parse".fmt(f),
+            Stage::Part(part) => write!(f, "part{}", part)
+        }
+    }
+}
+
+impl Stage {
+    fn from_str(s: &str) -> Option<Stage> {
+        if s == "parse" {
+            Some(Stage::Parse)
+        }
+        else {
+            s.strip_prefix("part")?.parse::<u32>().ok().map(Stage::Part)
+        }
+    }
+}
+
+/// Wall times over a number of runs.
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1]
+        }
+    }
+}
+
+/// Calls `f` `iterations` times (at least once), returning the timings and
+/// the result of the last call.
+pub fn time<R, F: FnMut() -> R>(iterations: u32, mut f: F) -> (Stats, R) {
+    let mut samples = Vec::new();
+    let mut result = None;
+    for _ in 0..std::cmp::max(1, iterations) {
+        let start = Instant::now();
+        result = Some(f());
+        samples.push(start.elapsed());
+    }
+    (Stats::from_samples(samples), result.unwrap())
+}
+
+/// A short human readable duration such as `1.25ms`.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    }
+    else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    }
+    else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    }
+    else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Median times recorded by an earlier run.
+#[derive(Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, Stage), Duration>,
+}
+
+impl Baseline {
+    /// Reads a baseline file. A missing file is an empty baseline.
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(e) => return Err(e)
+        };
+
+        let mut baseline = Baseline::default();
+        for (line, number) in text.lines().zip(1..) {
+            let fields : Vec<&str> = line.split_whitespace().collect();
+            let entry = match fields[..] {
+                [day, stage, nanos] => {
+                    day.parse::<u32>().ok()
+                        .and_then(|day| Some((day, Stage::from_str(stage)?)))
+                        .and_then(|key| Some((key, Duration::from_nanos(nanos.parse::<u64>().ok()?))))
+                },
+                [] => continue,
+                _ => None
+            };
+            let (key, median) = entry.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData,
+                               format!("{}:{}: expected <day> <stage> <nanoseconds>", path.display(), number))
+            })?;
+            baseline.medians.insert(key, median);
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = String::new();
+        for ((day, stage), median) in self.medians.iter() {
+            text.push_str(&format!("{} {} {}\n", day, stage, median.as_nanos()));
+        }
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: u32, stage: Stage) -> Option<Duration> {
+        self.medians.get(&(day, stage)).cloned()
+    }
+
+    pub fn set(&mut self, day: u32, stage: Stage, median: Duration) {
+        self.medians.insert((day, stage), median);
+    }
+}
+
+/// How `median` compares to the baseline `before`, as a ratio.
+pub fn ratio(median: Duration, before: Duration) -> f64 {
+    median.as_secs_f64() / before.as_secs_f64().max(1e-9)
+}
+
+/// Whether `median` is far enough over the baseline `before` to count as a
+/// regression.
+pub fn regressed(median: Duration, before: Duration) -> bool {
+    ratio(median, before) > REGRESSION_THRESHOLD
+}
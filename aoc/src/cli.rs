@@ -0,0 +1,61 @@
+//! A small command line parser: `--name value` options, `--name`
+//! switches and positional arguments, in any order.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+pub struct Args {
+    values: HashMap<&'static str, String>,
+    switches: HashSet<&'static str>,
+    pub positional: Vec<String>,
+}
+
+impl Args {
+    /// Parses `args`, accepting the options named in `with_value` and the
+    /// switches named in `switches` (both without the leading `--`).
+    pub fn parse<I>(args: I, with_value: &[&'static str], switches: &[&'static str]) -> Result<Args, String>
+    where I: IntoIterator<Item = String>
+    {
+        let mut result = Args {
+            values: HashMap::new(),
+            switches: HashSet::new(),
+            positional: Vec::new()
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") || arg.len() == 2 {
+                result.positional.push(arg);
+                continue;
+            }
+            let name = &arg[2..];
+            if let Some(name) = with_value.iter().find(|n| **n == name) {
+                let value = args.next().ok_or_else(|| format!("--{} needs a value", name))?;
+                result.values.insert(name, value);
+            }
+            else if let Some(name) = switches.iter().find(|n| **n == name) {
+                result.switches.insert(name);
+            }
+            else {
+                return Err(format!("unknown option {}", arg));
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|v| v.as_str())
+    }
+
+    /// The value of option `name` parsed as a `T`, if it was given.
+    pub fn parsed<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.value(name) {
+            Some(value) => value.parse::<T>().map(Some)
+                .map_err(|_| format!("invalid value for --{}: {}", name, value)),
+            None => Ok(None)
+        }
+    }
+
+    pub fn switch(&self, name: &str) -> bool {
+        self.switches.contains(name)
+    }
+}
@@ -0,0 +1,81 @@
+//! What the fuzz targets in `fuzz/` check, and the corpus they start from.
+//! Whatever bytes a day is given, reading them has to come to an input or
+//! a `ParseError`, and never to a panic. The same goes for linting them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use aoc_common::{InputShape, ParseError};
+
+use crate::{manifest, Day};
+
+// Seeds from the puzzle input proper only for days with nothing smaller
+const PUZZLE_INPUT : &str = "input.txt";
+
+/// Reads `data` as `day`'s input the way its fuzz target does: as UTF-8,
+/// with values joined by single spaces as they would be from the command
+/// line. Panics if the day's parser or linter does, or if an error they
+/// give points outside the input.
+pub fn parse(day: &Day, data: &[u8]) {
+    let text = match str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return
+    };
+    let text = match day.solver.input_shape() {
+        InputShape::File => text.to_string(),
+        InputShape::Values(_) => text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    };
+
+    let lines = text.lines().count();
+    let check = |e: &ParseError| {
+        assert!(e.line >= 1 && e.line <= lines + 1 && e.col >= 1,
+                "day {}: {:?} points at {}:{}, outside the input", day.number, e.message, e.line, e.col);
+    };
+    if let Err(e) = day.solver.parse_input(&text) {
+        check(&e);
+    }
+    for e in day.solver.lint_input(&text) {
+        check(&e);
+    }
+}
+
+/// The inputs `day`'s manifest names, by file name, or for days that take
+/// values by the values joined with `_`. The puzzle input is left out if
+/// there's anything else.
+pub fn seeds(day: &Day) -> io::Result<BTreeMap<String, String>> {
+    let dir = manifest::day_dir(day.number);
+    let mut seeds = BTreeMap::new();
+    for entry in manifest::load(&dir)? {
+        let (input, _) = manifest::load_input(day, &dir, &entry.args)?;
+        let name = match day.solver.input_shape() {
+            InputShape::File => Path::new(&input.name).file_name().map_or(input.name.clone(), |name| {
+                name.to_string_lossy().into_owned()
+            }),
+            InputShape::Values(_) => input.text.replace(' ', "_")
+        };
+        seeds.insert(name, input.text);
+    }
+    if seeds.keys().any(|name| name != PUZZLE_INPUT) {
+        seeds.remove(PUZZLE_INPUT);
+    }
+    Ok(seeds)
+}
+
+/// Writes every day's seeds to `dir`, in a directory per fuzz target, and
+/// returns the files written.
+pub fn seed_corpus(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for day in crate::DAYS {
+        let target = dir.join(format!("day_{:02}", day.number));
+        fs::create_dir_all(&target)?;
+        for (name, text) in seeds(day)? {
+            let path = target.join(name);
+            fs::write(&path, text)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,70 @@
+//! Registry of every implemented day, used by the `aoc` runner.
+
+extern crate aoc_common;
+extern crate day_01;
+extern crate day_02;
+extern crate day_03;
+extern crate day_04;
+extern crate day_05;
+extern crate day_06;
+extern crate day_07;
+extern crate day_08;
+extern crate day_09;
+extern crate day_10;
+extern crate day_11;
+extern crate day_12;
+extern crate day_13;
+extern crate day_14;
+extern crate day_15;
+
+pub mod all;
+pub mod batch;
+pub mod bench;
+pub mod cli;
+pub mod fuzz;
+pub mod manifest;
+pub mod scaffold;
+pub mod serve;
+pub mod watch;
+
+use aoc_common::{Solution, Solver};
+
+/// One implemented day and how to drive it.
+pub struct Day {
+    pub number: u32,
+    pub solver: &'static dyn Solver,
+}
+
+macro_rules! day {
+    ($solution:path) => {
+        Day {
+            number: <$solution as Solution>::DAY,
+            solver: &$solution
+        }
+    };
+}
+
+/// The parts every day answers.
+pub const PARTS : &[u32] = &[1, 2];
+
+pub const DAYS : &[Day] = &[
+    day!(day_01::Day01),
+    day!(day_02::Day02),
+    day!(day_03::Day03),
+    day!(day_04::Day04),
+    day!(day_05::Day05),
+    day!(day_06::Day06),
+    day!(day_07::Day07),
+    day!(day_08::Day08),
+    day!(day_09::Day09),
+    day!(day_10::Day10),
+    day!(day_11::Day11),
+    day!(day_12::Day12),
+    day!(day_13::Day13),
+    day!(day_14::Day14),
+    day!(day_15::Day15),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,414 @@
+extern crate aoc;
+extern crate aoc_common;
+
+use std::io;
+use std::net::TcpListener;
+use std::path::Path;
+use std::process;
+
+use aoc::all::{self, Outcome, Status};
+use aoc::batch;
+use aoc::bench::{self, Baseline, Stage};
+use aoc::cli::Args;
+use aoc::fuzz;
+use aoc::scaffold;
+use aoc::serve;
+use aoc::watch;
+use aoc_common::{params, trace, Format, Input, InputShape, Params};
+
+const DEFAULT_ITERATIONS : u32 = 10;
+const DEFAULT_SEED : u64 = 1;
+const DEFAULT_SIZE : usize = 10;
+
+fn usage(prog_name: &str) {
+    eprintln!("Usage: {} list", prog_name);
+    eprintln!("       {} run --day <n> [--part <p>] [--format text|json] [--explain] [--set <name>=<value>]... <input>...", prog_name);
+    eprintln!("       {} run --day <n> [--part <p>] [--set <name>=<value>]... --bench [--iterations <n>]", prog_name);
+    eprintln!("              [--baseline <file>] [--save-baseline <file>] <input>...");
+    eprintln!("       {} all [--threads <n>]", prog_name);
+    eprintln!("       {} batch --day <n> [--check] [--set <name>=<value>]... <input_file>...", prog_name);
+    eprintln!("       {} lint --day <n> <input_file>...", prog_name);
+    eprintln!("       {} serve --port <n>", prog_name);
+    eprintln!("       {} new --day <n>", prog_name);
+    eprintln!("       {} gen --day <n> [--seed <s>] [--size <k>]", prog_name);
+    eprintln!("       {} seed-corpus [--dir <dir>]", prog_name);
+    eprintln!("       {} watch --day <n> [--part <p>] [--set <name>=<value>]... <input_file>", prog_name);
+    eprintln!("run, all, batch and watch also take -v, -vv and --trace <target> to show trace points");
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+// Every day, with its parameters under it
+fn list() {
+    println!("Day  Parts  Input");
+    for day in aoc::DAYS {
+        let parts = aoc::PARTS.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(" ");
+        println!("{:>3}  {:<5}  {}", day.number, parts, day.solver.input_shape().usage());
+        for param in day.solver.params() {
+            println!("       --set {}={}  {} ({} to {})", param.name, param.default, param.about, param.min, param.max);
+        }
+    }
+}
+
+// The day chosen with --day, and the parts chosen with --part
+fn day_and_parts(prog_name: &str, args: &Args) -> (&'static aoc::Day, Vec<u32>) {
+    let number = match args.parsed::<u32>("day") {
+        Ok(Some(number)) => number,
+        Ok(None) => { usage(prog_name); process::exit(1); },
+        Err(e) => fail(&e)
+    };
+    let day = aoc::find(number).unwrap_or_else(|| fail(&format!("day {} is not implemented", number)));
+
+    let parts = match args.parsed::<u32>("part") {
+        Ok(Some(part)) if aoc::PARTS.contains(&part) => vec![part],
+        Ok(Some(part)) => fail(&format!("day {} has no part {}", number, part)),
+        Ok(None) => aoc::PARTS.to_vec(),
+        Err(e) => fail(&e)
+    };
+    (day, parts)
+}
+
+fn run(prog_name: &str, args: Args, set: Params) -> io::Result<()> {
+    let (day, parts) = day_and_parts(prog_name, &args);
+    let number = day.number;
+
+    let shape = day.solver.input_shape();
+    let input = match shape.load(args.positional.iter().cloned()) {
+        Some(input) => input?,
+        None => {
+            eprintln!("Usage: {} run --day {} [--part <p>] {}", prog_name, number, shape.usage());
+            process::exit(1);
+        }
+    };
+    let mut params = Params::for_input(&input.name)?;
+    params.extend(&set);
+    params.check(day.number, day.solver.params()).unwrap_or_else(|e| fail(&e));
+
+    if args.switch("bench") {
+        return run_bench(day, &input, &params, &parts, &args);
+    }
+
+    let format = args.parsed::<Format>("format").unwrap_or_else(|e| fail(&e)).unwrap_or(Format::Text);
+    let parsed = day.solver.parse_input_with(&input.text, &params).unwrap_or_else(|e| {
+        aoc_common::exit_with(day.number, e, &input, format)
+    });
+    for part in parts {
+        let (answer, elapsed) = aoc_common::timed(|| day.solver.answer(&*parsed, part));
+        aoc_common::report(format, day.number, part, &*answer, elapsed, args.switch("explain"));
+    }
+    Ok(())
+}
+
+// Times parsing and each part separately, then compares the medians with
+// the baseline if there is one.
+fn run_bench(day: &aoc::Day, input: &Input, params: &Params, parts: &[u32], args: &Args) -> io::Result<()> {
+    let iterations = args.parsed::<u32>("iterations").unwrap_or_else(|e| fail(&e))
+        .unwrap_or(DEFAULT_ITERATIONS);
+    let baseline = match args.value("baseline") {
+        Some(path) => Baseline::load(Path::new(path))?,
+        None => Baseline::default()
+    };
+
+    let mut timings = Vec::new();
+    let (stats, parsed) = bench::time(iterations, || day.solver.parse_input_with(&input.text, params));
+    let parsed = parsed.unwrap_or_else(|e| aoc_common::exit_with(day.number, e, input, Format::Text));
+    timings.push((Stage::Parse, stats));
+    for &part in parts {
+        let (stats, answer) = bench::time(iterations, || day.solver.answer(&*parsed, part));
+        aoc_common::print_answer(part, answer);
+        timings.push((Stage::Part(part), stats));
+    }
+
+    println!();
+    println!("Day {}, {} iterations", day.number, iterations);
+    println!("{:<6} {:>10} {:>10} {:>10}", "Stage", "Min", "Median", "Max");
+    let mut regressions = 0;
+    for (stage, stats) in timings.iter() {
+        let mut line = format!("{:<6} {:>10} {:>10} {:>10}", stage.to_string(),
+                               bench::format_duration(stats.min),
+                               bench::format_duration(stats.median),
+                               bench::format_duration(stats.max));
+        if let Some(before) = baseline.get(day.number, *stage) {
+            let ratio = bench::ratio(stats.median, before);
+            line.push_str(&format!("  {:+.0}% vs baseline", (ratio - 1.0) * 100.0));
+            if bench::regressed(stats.median, before) {
+                line.push_str(", REGRESSION");
+                regressions += 1;
+            }
+        }
+        println!("{}", line);
+    }
+
+    if let Some(path) = args.value("save-baseline") {
+        let path = Path::new(path);
+        let mut saved = Baseline::load(path)?;
+        for (stage, stats) in timings {
+            saved.set(day.number, stage, stats.median);
+        }
+        saved.save(path)?;
+    }
+
+    if regressions > 0 {
+        fail(&format!("{} stage(s) got slower than the baseline", regressions));
+    }
+    Ok(())
+}
+
+// How an answer fits in a table cell: multi-line answers (day 10's
+// message) are too tall to show.
+fn cell(answer: &Option<String>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => format!("({} lines)", answer.trim().lines().count()),
+        Some(answer) => answer.clone(),
+        None => "-".to_string()
+    }
+}
+
+// Prints a row for each outcome, headed by its label, and returns how many
+// failed or gave a wrong answer. Answers without an expected value are
+// only called out if `checked`.
+fn print_table(label_heading: &str, labels: &[String], outcomes: &[Outcome], checked: bool) -> usize {
+    let rows : Vec<[String; 5]> = labels.iter().zip(outcomes.iter()).map(|(label, outcome)| {
+        let status = match &outcome.status {
+            Status::Ok => "OK".to_string(),
+            Status::Unchecked if checked => "OK (unchecked)".to_string(),
+            Status::Unchecked => "OK".to_string(),
+            Status::Mismatch(parts) => format!("MISMATCH in part {}",
+                                               parts.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(", ")),
+            Status::NoInput => "skipped (no input)".to_string(),
+            Status::Error(message) => format!("ERROR: {}", message)
+        };
+        [label.clone(), cell(&outcome.answers[0]), cell(&outcome.answers[1]),
+         outcome.elapsed.map_or("-".to_string(), bench::format_duration), status]
+    }).collect();
+
+    let headings = [label_heading, "Part 1", "Part 2", "Time", "Status"];
+    let widths : Vec<usize> = (0..headings.len()).map(|column| {
+        rows.iter().map(|row| row[column].chars().count())
+            .chain(std::iter::once(headings[column].len())).max().unwrap()
+    }).collect();
+    let print_row = |row: &[&str]| {
+        let line = format!("{:>w0$}  {:<w1$}  {:<w2$}  {:>w3$}  {}", row[0], row[1], row[2], row[3], row[4],
+                           w0 = widths[0], w1 = widths[1], w2 = widths[2], w3 = widths[3]);
+        println!("{}", line.trim_end());
+    };
+    print_row(&headings);
+    for row in rows.iter() {
+        print_row(&row.iter().map(|s| s.as_str()).collect::<Vec<&str>>());
+    }
+
+    outcomes.iter().filter(|outcome| {
+        matches!(outcome.status, Status::Mismatch(_) | Status::Error(_))
+    }).count()
+}
+
+// Runs every day against its puzzle input and prints a table of the
+// results. Exits with 1 if any day failed or gave a wrong answer.
+fn run_all(args: Args) {
+    let threads = args.parsed::<usize>("threads").unwrap_or_else(|e| fail(&e))
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    let outcomes = all::run_all(aoc::DAYS, threads);
+
+    let labels : Vec<String> = outcomes.iter().map(|outcome| outcome.day.to_string()).collect();
+    let failed = print_table("Day", &labels, &outcomes, true);
+    if failed > 0 {
+        fail(&format!("{} day(s) failed", failed));
+    }
+}
+
+// Runs one day over each of the files given and prints a table of the
+// results. Exits with 1 if any file failed, or with --check, gave an
+// answer other than the one in the answers.txt next to it.
+fn batch(prog_name: &str, args: Args, set: Params) {
+    let (day, _) = day_and_parts(prog_name, &args);
+    if let InputShape::Values(_) = day.solver.input_shape() {
+        fail(&format!("day {} takes values, not files", day.number));
+    }
+    if args.positional.is_empty() {
+        usage(prog_name);
+        process::exit(1);
+    }
+    let check = args.switch("check");
+
+    let files = batch::inputs(&args.positional);
+    let outcomes : Vec<Outcome> = files.iter().map(|file| {
+        batch::run_file(day, Path::new(file), &set, check)
+    }).collect();
+    let labels : Vec<String> = files.into_iter().cloned().collect();
+    let failed = print_table("File", &labels, &outcomes, check);
+    if failed > 0 {
+        fail(&format!("{} file(s) failed", failed));
+    }
+}
+
+// Checks the structure of each file given as input for a day, printing
+// every problem found. Exits with 1 if there were any.
+fn lint(prog_name: &str, args: Args) {
+    let (day, _) = day_and_parts(prog_name, &args);
+    if let InputShape::Values(_) = day.solver.input_shape() {
+        fail(&format!("day {} takes values, not files", day.number));
+    }
+    if args.positional.is_empty() {
+        usage(prog_name);
+        process::exit(1);
+    }
+
+    let mut problems = 0;
+    for name in args.positional.iter() {
+        let text = std::fs::read_to_string(name).unwrap_or_else(|e| fail(&format!("can't read {}: {}", name, e)));
+        let errors = day.solver.lint_input(&text);
+        for error in errors.iter() {
+            eprintln!("{}\n", error.clone().in_file(name));
+        }
+        if errors.is_empty() {
+            println!("{}: no problems found", name);
+        }
+        else {
+            println!("{}: {} problem(s)", name, errors.len());
+        }
+        problems += errors.len();
+    }
+    if problems > 0 {
+        process::exit(1);
+    }
+}
+
+// Answers requests for solutions over HTTP on the loopback interface
+fn serve(prog_name: &str, args: Args) -> io::Result<()> {
+    let port = match args.parsed::<u16>("port") {
+        Ok(Some(port)) => port,
+        Ok(None) => { usage(prog_name); process::exit(1); },
+        Err(e) => fail(&e)
+    };
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("listening on http://{}", listener.local_addr()?);
+    serve::serve(listener)
+}
+
+// Starts a new day from the template and registers it
+fn new_day(prog_name: &str, args: Args) -> io::Result<()> {
+    let number = match args.parsed::<u32>("day") {
+        Ok(Some(number)) => number,
+        Ok(None) => { usage(prog_name); process::exit(1); },
+        Err(e) => fail(&e)
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let written = scaffold::create(&root, number).unwrap_or_else(|e| fail(&e.to_string()));
+    for path in written {
+        let path = path.strip_prefix(&root).unwrap_or(&path);
+        println!("wrote {}", path.display());
+    }
+    println!("Fill in day_{:02}/src/lib.rs, then: cargo run -p day_{:02} -- day_{:02}/test.txt", number, number, number);
+    Ok(())
+}
+
+// Writes the fuzz targets' starting corpus, by default where `cargo fuzz`
+// looks for it
+fn seed_corpus(args: Args) -> io::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let dir = args.value("dir").map_or_else(|| root.join("fuzz").join("corpus"), |dir| Path::new(dir).to_path_buf());
+    let written = fuzz::seed_corpus(&dir)?;
+    println!("wrote {} seeds to {}", written.len(), dir.strip_prefix(&root).unwrap_or(&dir).display());
+    Ok(())
+}
+
+// Prints a made-up input for a day. The same seed and size always give
+// the same input.
+fn generate(prog_name: &str, args: Args) {
+    let number = match args.parsed::<u32>("day") {
+        Ok(Some(number)) => number,
+        Ok(None) => { usage(prog_name); process::exit(1); },
+        Err(e) => fail(&e)
+    };
+    let day = aoc::find(number).unwrap_or_else(|| fail(&format!("day {} is not implemented", number)));
+    let seed = args.parsed::<u64>("seed").unwrap_or_else(|e| fail(&e)).unwrap_or(DEFAULT_SEED);
+    let size = args.parsed::<usize>("size").unwrap_or_else(|e| fail(&e)).unwrap_or(DEFAULT_SIZE);
+
+    let text = day.solver.generate_input(seed, size);
+    println!("{}", text.trim_end_matches('\n'));
+}
+
+// Solves a day's input file every time it changes
+fn watch(prog_name: &str, args: Args, set: Params) -> ! {
+    let (day, parts) = day_and_parts(prog_name, &args);
+    if let InputShape::Values(_) = day.solver.input_shape() {
+        fail(&format!("day {} takes values, not a file to watch", day.number));
+    }
+    let path = match args.positional.as_slice() {
+        [path] => Path::new(path),
+        _ => { usage(prog_name); process::exit(1); }
+    };
+    if !path.is_file() {
+        fail(&format!("can't read {}", path.display()));
+    }
+    watch::watch(day, path, &parts, &set)
+}
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args();
+    let prog_name = args.next().unwrap_or_else(|| "aoc".to_string());
+
+    match args.next().as_deref() {
+        Some("list") => {
+            list();
+            Ok(())
+        },
+        Some("run") => {
+            let mut set = Params::default();
+            let args = trace::configure(args).and_then(|args| params::configure(args, &mut set)).and_then(|args| {
+                Args::parse(args, &["day", "part", "format", "iterations", "baseline", "save-baseline"], &["bench", "explain"])
+            }).unwrap_or_else(|e| fail(&e));
+            run(&prog_name, args, set)
+        },
+        Some("all") => {
+            let args = trace::configure(args).and_then(|args| Args::parse(args, &["threads"], &[]))
+                .unwrap_or_else(|e| fail(&e));
+            run_all(args);
+            Ok(())
+        },
+        Some("batch") => {
+            let mut set = Params::default();
+            let args = trace::configure(args).and_then(|args| params::configure(args, &mut set))
+                .and_then(|args| Args::parse(args, &["day"], &["check"]))
+                .unwrap_or_else(|e| fail(&e));
+            batch(&prog_name, args, set);
+            Ok(())
+        },
+        Some("watch") => {
+            let mut set = Params::default();
+            let args = trace::configure(args).and_then(|args| params::configure(args, &mut set))
+                .and_then(|args| Args::parse(args, &["day", "part"], &[]))
+                .unwrap_or_else(|e| fail(&e));
+            watch(&prog_name, args, set)
+        },
+        Some("lint") => {
+            let args = Args::parse(args, &["day"], &[]).unwrap_or_else(|e| fail(&e));
+            lint(&prog_name, args);
+            Ok(())
+        },
+        Some("serve") => {
+            let args = Args::parse(args, &["port"], &[]).unwrap_or_else(|e| fail(&e));
+            serve(&prog_name, args)
+        },
+        Some("new") => {
+            let args = Args::parse(args, &["day"], &[]).unwrap_or_else(|e| fail(&e));
+            new_day(&prog_name, args)
+        },
+        Some("seed-corpus") => {
+            let args = Args::parse(args, &["dir"], &[]).unwrap_or_else(|e| fail(&e));
+            seed_corpus(args)
+        },
+        Some("gen") => {
+            let args = Args::parse(args, &["day", "seed", "size"], &[]).unwrap_or_else(|e| fail(&e));
+            generate(&prog_name, args);
+            Ok(())
+        },
+        _ => {
+            usage(&prog_name);
+            process::exit(1);
+        }
+    }
+}
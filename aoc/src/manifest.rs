@@ -0,0 +1,97 @@
+//! The expected answers checked in next to each day's inputs, in
+//! `day_NN/answers.txt`. Each line reads
+//!
+//! ```text
+//! <part> <expected answer> <input...>
+//! ```
+//!
+//! where the input is whatever the day takes on the command line: a file
+//! name relative to the day's directory, or literal values, optionally
+//! after `--set <name>=<value>` parameters. Blank lines and lines starting
+//! with `#` are ignored.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::params;
+use aoc_common::{Input, InputShape, Params};
+
+use crate::Day;
+
+pub const FILE_NAME : &str = "answers.txt";
+
+pub struct Entry {
+    /// Line number in the manifest, for reporting.
+    pub line: usize,
+    pub part: u32,
+    pub expected: String,
+    pub args: Vec<String>,
+}
+
+impl Entry {
+    /// Runs `day` on this entry's input and returns the answer it gives.
+    /// File names are resolved relative to `dir`.
+    pub fn solve(&self, day: &Day, dir: &Path) -> io::Result<String> {
+        let (input, params) = load_input(day, dir, &self.args)?;
+        let parsed = day.solver.parse_input_with(&input.text, &params).map_err(|e| e.in_file(&input.name))?;
+        Ok(day.solver.answer(&*parsed, self.part).to_string())
+    }
+}
+
+/// Loads the input `day` would be given for `args`, resolving file names
+/// relative to `dir`, and the parameters to solve it with: those in the
+/// input's `.params` file, then those set in `args`.
+pub fn load_input(day: &Day, dir: &Path, args: &[String]) -> io::Result<(Input, Params)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let mut set = Params::default();
+    let args = params::configure(args.iter().cloned(), &mut set).map_err(invalid)?;
+
+    let shape = day.solver.input_shape();
+    let args = args.into_iter().map(|arg| match shape {
+        InputShape::File => dir.join(arg).to_string_lossy().into_owned(),
+        InputShape::Values(_) => arg
+    });
+    let input = shape.load(args).unwrap_or_else(|| Err(invalid(format!("expected {}", shape.usage()))))?;
+
+    let mut params = Params::for_input(&input.name)?;
+    params.extend(&set);
+    params.check(day.number, day.solver.params()).map_err(invalid)?;
+    Ok((input, params))
+}
+
+/// The directory holding a day's inputs and manifest, found from the
+/// location of this crate's sources.
+pub fn day_dir(number: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day_{:02}", number))
+}
+
+pub fn parse(text: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let part = fields.next().unwrap().parse::<u32>()
+            .map_err(|_| format!("line {}: the part must be a number", line_number))?;
+        let expected = fields.next()
+            .ok_or_else(|| format!("line {}: missing the expected answer", line_number))?;
+        entries.push(Entry {
+            line: line_number,
+            part,
+            expected: expected.to_string(),
+            args: fields.map(|field| field.to_string()).collect()
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads the manifest in `dir`.
+pub fn load(dir: &Path) -> io::Result<Vec<Entry>> {
+    let text = fs::read_to_string(dir.join(FILE_NAME))?;
+    parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,203 @@
+//! `aoc new`: starts a new day. Writes a `day_NN` crate whose `Solution`
+//! builds and answers 0 until it's filled in, with empty `test.txt` and
+//! `input.txt` files and a manifest of stub entries to match. The day is
+//! registered with the workspace, this crate's dependencies and `DAYS`,
+//! and gets a fuzz target.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Days run from 1 to 25.
+pub const LAST_DAY : u32 = 25;
+
+const CARGO_TOML : &str = r#"[package]
+name = "day_{NN}"
+version.workspace = true
+authors.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc_common = { path = "../aoc_common" }
+"#;
+
+const MAIN_RS : &str = r#"extern crate aoc_common;
+extern crate day_{NN};
+
+use std::io;
+
+fn main() -> io::Result<()> {
+    aoc_common::run(&day_{NN}::Day{NN})
+}
+"#;
+
+const LIB_RS : &str = r#"extern crate aoc_common;
+
+use aoc_common::{ParseError, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    const DAY : u32 = {N};
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(&self, _lines: &Vec<String>) -> usize {
+        0
+    }
+
+    fn part2(&self, _lines: &Vec<String>) -> usize {
+        0
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| format!("{}\n", rng.below(100))).collect()
+    }
+}
+"#;
+
+const FUZZ_TARGET : &str = r#"#![no_main]
+
+extern crate aoc;
+#[macro_use]
+extern crate libfuzzer_sys;
+
+fuzz_target!(|data: &[u8]| {
+    aoc::fuzz::parse(aoc::find({N}).unwrap(), data);
+});
+"#;
+
+const FUZZ_BIN : &str = r#"[[bin]]
+name = "day_{NN}"
+path = "fuzz_targets/day_{NN}.rs"
+test = false
+doc = false
+bench = false"#;
+
+// The template answers 0 for everything, so these hold until it's filled in
+const ANSWERS_TXT : &str = "# <part> <expected answer> <input...>
+# Stubs, until test.txt holds the example and these its answers
+1 0 test.txt
+2 0 test.txt
+";
+
+// A template with the day's number filled in: `{NN}` as two digits and
+// `{N}` as it is
+fn fill(template: &str, number: u32) -> String {
+    template.replace("{NN}", &format!("{:02}", number)).replace("{N}", &number.to_string())
+}
+
+// The day a line names, if it names one as `day_NN`
+fn day_in(line: &str) -> Option<u32> {
+    let start = line.find("day_")? + "day_".len();
+    line.get(start..start + 2)?.parse().ok()
+}
+
+// Inserts `new_item` after the last entry naming a day before `number`,
+// or before the first entry if there's none before it
+fn insert_item<'a>(items: &mut Vec<&'a str>, is_entry: impl Fn(&str) -> bool, number: u32, new_item: &'a str) -> io::Result<()> {
+    let entries : Vec<usize> = (0..items.len()).filter(|&i| is_entry(items[i])).collect();
+    let first = *entries.first().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("found nowhere to add {:?}", new_item.trim()))
+    })?;
+    let at = entries.iter().rev().find(|&&i| day_in(items[i]).is_some_and(|day| day < number))
+        .map_or(first, |&i| i + 1);
+    items.insert(at, new_item);
+    Ok(())
+}
+
+// Inserts `new_line` among the lines naming days, in order
+fn insert_line(text: &str, is_entry: impl Fn(&str) -> bool, number: u32, new_line: &str) -> io::Result<String> {
+    let mut lines : Vec<&str> = text.lines().collect();
+    insert_item(&mut lines, is_entry, number, new_line)?;
+    Ok(lines.join("\n") + "\n")
+}
+
+// Adds the day's fuzz target among the others, which are tables separated
+// by blank lines
+fn add_target(text: &str, number: u32) -> io::Result<String> {
+    let mut tables : Vec<&str> = text.trim_end().split("\n\n").collect();
+    let table = fill(FUZZ_BIN, number);
+    insert_item(&mut tables, |table| table.starts_with("[[bin]]") && day_in(table).is_some(), number, &table)?;
+    Ok(tables.join("\n\n") + "\n")
+}
+
+// Adds the day to the workspace members, which are written five days to
+// a line
+fn add_member(text: &str, number: u32) -> io::Result<String> {
+    let is_days = |line: &str| {
+        let line = line.trim();
+        !line.is_empty() && line.split(',').map(str::trim).filter(|entry| !entry.is_empty())
+            .all(|entry| entry.starts_with("\"day_"))
+    };
+    let lines : Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|line| is_days(line)).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "found no days among the workspace members")
+    })?;
+    let end = start + lines[start..].iter().take_while(|line| is_days(line)).count();
+
+    let mut days : Vec<u32> = lines[start..end].iter().flat_map(|line| line.split(',')).filter_map(day_in).collect();
+    days.push(number);
+    days.sort();
+    let rows = days.chunks(5).map(|row| {
+        format!("    {},", row.iter().map(|day| format!("\"day_{:02}\"", day)).collect::<Vec<String>>().join(", "))
+    });
+
+    let mut result : Vec<String> = lines[..start].iter().map(|line| line.to_string()).collect();
+    result.extend(rows);
+    result.extend(lines[end..].iter().map(|line| line.to_string()));
+    Ok(result.join("\n") + "\n")
+}
+
+/// Creates day `number` in the workspace at `root`, returning the files it
+/// wrote or changed. Fails without changing anything if the day already
+/// exists, or if it can't see where to register it.
+pub fn create(root: &Path, number: u32) -> io::Result<Vec<PathBuf>> {
+    if !(1..=LAST_DAY).contains(&number) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("days run from 1 to {}", LAST_DAY)));
+    }
+    let name = format!("day_{:02}", number);
+    let dir = root.join(&name);
+    let workspace = root.join("Cargo.toml");
+    let dependencies = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("lib.rs");
+    let fuzz = root.join("fuzz");
+
+    let registered = fs::read_to_string(&registry)?;
+    if dir.exists() || registered.contains(&format!("extern crate {};", name)) {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", name)));
+    }
+
+    // Every change is worked out before anything is written
+    let mut changes = vec![
+        (dir.join("Cargo.toml"), fill(CARGO_TOML, number)),
+        (dir.join("src").join("main.rs"), fill(MAIN_RS, number)),
+        (dir.join("src").join("lib.rs"), fill(LIB_RS, number)),
+        (dir.join("answers.txt"), ANSWERS_TXT.to_string()),
+        (dir.join("test.txt"), String::new()),
+        (dir.join("input.txt"), String::new()),
+    ];
+    changes.push((fuzz.join("fuzz_targets").join(format!("{}.rs", name)), fill(FUZZ_TARGET, number)));
+    changes.push((fuzz.join("Cargo.toml"), add_target(&fs::read_to_string(fuzz.join("Cargo.toml"))?, number)?));
+    changes.push((workspace.clone(), add_member(&fs::read_to_string(&workspace)?, number)?));
+    changes.push((dependencies.clone(), insert_line(&fs::read_to_string(&dependencies)?, |line| line.starts_with("day_"),
+                                                    number, &format!("{} = {{ path = \"../{}\" }}", name, name))?));
+    let registered = insert_line(&registered, |line| line.starts_with("extern crate day_"), number,
+                                 &format!("extern crate {};", name))?;
+    changes.push((registry, insert_line(&registered, |line| line.trim_start().starts_with("day!(day_"), number,
+                                        &format!("    day!({}::Day{:02}),", name, number))?));
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(fuzz.join("fuzz_targets"))?;
+    for (path, contents) in changes.iter() {
+        fs::write(path, contents)?;
+    }
+    Ok(changes.into_iter().map(|(path, _)| path).collect())
+}
@@ -0,0 +1,190 @@
+//! `aoc serve`: the solvers behind a small HTTP service on the loopback
+//! interface, for tools that want answers without running a binary per
+//! day. There's one route:
+//!
+//! ```text
+//! POST /day/<n>/part/<p>[?<name>=<value>&...]
+//! ```
+//!
+//! with the puzzle input as the body, or the values for days that take
+//! values. Query pairs set the day's parameters. The reply is JSON: the
+//! same object `--format json` prints for an answer, or one with an
+//! `error` field, which for input that doesn't parse is the diagnostic
+//! with its line and column. Each connection takes one request.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::{InputShape, Json, Params};
+
+use crate::all::panic_message;
+
+/// The largest body accepted, which is far more than any puzzle input.
+pub const MAX_BODY : usize = 16 * 1024 * 1024;
+
+// How long a client may take to send its request
+const READ_TIMEOUT : Duration = Duration::from_secs(10);
+
+/// A reply: the HTTP status and the JSON body.
+pub struct Response {
+    pub status: u16,
+    pub body: Json,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response { status, body: Json::object(vec![("error", Json::object(vec![("message", message.into())]))]) }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Internal Server Error"
+    }
+}
+
+// The parameters set in a query string such as `workers=2&offset=0`
+fn query_params(query: &str) -> Result<Params, String> {
+    let mut params = Params::default();
+    for assignment in query.split('&').filter(|pair| !pair.is_empty()) {
+        params.assign(assignment)?;
+    }
+    Ok(params)
+}
+
+/// Answers one request, given its method, target (path and query) and
+/// body.
+pub fn respond(method: &str, target: &str, body: &str) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments : Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (day, part) = match segments.as_slice() {
+        ["day", day, "part", part] => (day, part),
+        _ => return Response::error(404, &format!("no such route {}; expected /day/<n>/part/<p>", path))
+    };
+    if method != "POST" {
+        return Response::error(405, "expected POST with the input as the body");
+    }
+    let day = match day.parse::<u32>().ok().and_then(crate::find) {
+        Some(day) => day,
+        None => return Response::error(404, &format!("day {} is not implemented", day))
+    };
+    let part = match part.parse::<u32>() {
+        Ok(part) if crate::PARTS.contains(&part) => part,
+        _ => return Response::error(404, &format!("day {} has no part {}", day.number, part))
+    };
+    let params = match query_params(query) {
+        Ok(params) => params,
+        Err(e) => return Response::error(400, &e)
+    };
+    if let Err(e) = params.check(day.number, day.solver.params()) {
+        return Response::error(400, &e);
+    }
+
+    // Values come joined by single spaces, as they would from the command line
+    let text = match day.solver.input_shape() {
+        InputShape::File => body.to_string(),
+        InputShape::Values(_) => body.split_whitespace().collect::<Vec<&str>>().join(" ")
+    };
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        day.solver.parse_input_with(&text, &params).map(|parsed| {
+            let (answer, elapsed) = aoc_common::timed(|| day.solver.answer(&*parsed, part));
+            aoc_common::answer_json(day.number, part, &*answer, elapsed)
+        })
+    }));
+    match solved {
+        Ok(Ok(body)) => Response { status: 200, body },
+        Ok(Err(e)) => Response { status: 422, body: Json::object(vec![
+            ("day", day.number.into()),
+            ("error", e.in_file("<request>").to_json()),
+        ]) },
+        Err(payload) => Response::error(500, &format!("part {}: {}", part, panic_message(payload)))
+    }
+}
+
+// Reads a request from the stream and works out the reply
+fn read_request(stream: &mut TcpStream) -> io::Result<Response> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut fields = request_line.split_whitespace();
+    let (method, target) = match (fields.next(), fields.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Ok(Response::error(400, "expected a request line such as POST /day/1/part/1 HTTP/1.1"))
+    };
+
+    let mut content_length = None;
+    let mut expect_continue = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+            if name == "content-length" {
+                match value.parse::<usize>() {
+                    Ok(length) => content_length = Some(length),
+                    Err(_) => return Ok(Response::error(400, "expected a number for Content-Length"))
+                }
+            }
+            else if name == "expect" && value.eq_ignore_ascii_case("100-continue") {
+                expect_continue = true;
+            }
+        }
+    }
+
+    let length = match content_length {
+        Some(length) if length > MAX_BODY => return Ok(Response::error(413, "the input is too big")),
+        Some(length) => length,
+        None if method == "POST" => return Ok(Response::error(411, "expected a Content-Length")),
+        None => 0
+    };
+    if expect_continue {
+        stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    match String::from_utf8(body) {
+        Ok(body) => Ok(respond(&method, &target, &body)),
+        Err(_) => Ok(Response::error(400, "expected the input as UTF-8"))
+    }
+}
+
+/// Reads one request from `stream`, answers it and closes the connection.
+pub fn handle(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = read_request(&mut stream)?;
+    let body = format!("{}\n", response.body);
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           response.status, reason(response.status), body.len(), body)?;
+    stream.flush()
+}
+
+/// Answers requests on `listener` until the process is killed, each
+/// connection on a thread of its own.
+pub fn serve(listener: TcpListener) -> ! {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || {
+                    if let Err(e) = handle(stream) {
+                        eprintln!("error: {}", e);
+                    }
+                });
+            },
+            // A client that gave up before it was accepted
+            Err(e) => eprintln!("error: {}", e)
+        }
+    }
+    unreachable!("a listener's connections never run out")
+}
@@ -0,0 +1,98 @@
+//! `aoc watch`: solves a day's input file again every time it's saved,
+//! for working on hand-made maps. The file is polled rather than watched
+//! with the OS, and answers are printed as text; nothing is drawn.
+
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_common::{Format, Input, Params};
+
+use crate::all::panic_message;
+use crate::Day;
+
+/// How often the file is looked at.
+pub const INTERVAL : Duration = Duration::from_millis(250);
+
+// What's compared between polls. The length catches edits that land in
+// the same tick of a coarse modification time.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Notices when a file changes.
+pub struct Watcher {
+    path: PathBuf,
+    last: Option<Stamp>,
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> Watcher {
+        Watcher { path: path.to_path_buf(), last: None }
+    }
+
+    /// Whether the file has changed since the last poll; the first poll
+    /// counts as a change. A file that's missing, as it can be for a
+    /// moment while an editor saves it, hasn't changed.
+    pub fn poll(&mut self) -> bool {
+        let stamp = match fs::metadata(&self.path) {
+            Ok(metadata) => Stamp { modified: metadata.modified().ok(), len: metadata.len() },
+            Err(_) => return false
+        };
+        let changed = self.last != Some(stamp);
+        self.last = Some(stamp);
+        changed
+    }
+}
+
+/// Reads the file and solves `parts` of it with the parameters in its
+/// `.params` file and then `set`, printing the answers, or what's wrong
+/// with the input if it doesn't parse. A solver that panics is reported
+/// rather than ending the watch.
+pub fn solve_once(day: &Day, path: &Path, parts: &[u32], set: &Params) -> io::Result<()> {
+    let name = path.to_string_lossy().into_owned();
+    let input = Input { text: fs::read_to_string(path)?, name };
+    let mut params = Params::for_input(&input.name)?;
+    params.extend(set);
+    if let Err(e) = params.check(day.number, day.solver.params()) {
+        eprintln!("error: {}", e);
+        return Ok(());
+    }
+
+    let parsed = match day.solver.parse_input_with(&input.text, &params) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e.in_file(&input.name));
+            return Ok(());
+        }
+    };
+    for &part in parts {
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+            aoc_common::timed(|| day.solver.answer(&*parsed, part))
+        }));
+        match solved {
+            Ok((answer, elapsed)) => aoc_common::report(Format::Text, day.number, part, &*answer, elapsed, false),
+            Err(payload) => eprintln!("part {}: {}", part, panic_message(payload))
+        }
+    }
+    Ok(())
+}
+
+/// Solves the file now and again whenever it changes, until killed.
+pub fn watch(day: &Day, path: &Path, parts: &[u32], set: &Params) -> ! {
+    let mut watcher = Watcher::new(path);
+    loop {
+        if watcher.poll() {
+            println!("-- {} --", path.display());
+            if let Err(e) = solve_once(day, path, parts, set) {
+                eprintln!("error: {}", e);
+            }
+        }
+        thread::sleep(INTERVAL);
+    }
+}
@@ -0,0 +1,38 @@
+//! `aoc all` over a few quick days.
+
+extern crate aoc;
+
+use aoc::all::{self, Status};
+use aoc::manifest;
+
+#[test]
+fn outcomes_come_back_in_day_order() {
+    let days = &aoc::DAYS[..4];
+    let outcomes = all::run_all(days, 3);
+
+    let numbers : Vec<u32> = outcomes.iter().map(|outcome| outcome.day).collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+    for outcome in outcomes {
+        assert_eq!(outcome.status, Status::Ok, "day {}", outcome.day);
+        assert!(outcome.answers.iter().all(Option::is_some));
+    }
+}
+
+#[test]
+fn every_day_has_a_puzzle_input() {
+    for day in aoc::DAYS {
+        let dir = manifest::day_dir(day.number);
+        let entries = manifest::load(&dir).unwrap();
+        assert!(all::puzzle_args(day, &dir, &entries).is_ok(), "day {}", day.number);
+    }
+}
+
+#[test]
+fn days_without_an_input_file_run_on_their_manifest() {
+    for &number in &[9, 11, 14] {
+        let outcome = all::run_day(aoc::find(number).unwrap());
+        assert!(outcome.status == Status::Ok || outcome.status == Status::Unchecked,
+                "day {}: {:?}", outcome.day, outcome.status);
+        assert!(outcome.answers.iter().all(Option::is_some));
+    }
+}
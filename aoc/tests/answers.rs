@@ -0,0 +1,45 @@
+//! Runs every day against the expected answers in its `answers.txt`.
+
+extern crate aoc;
+
+use aoc::manifest;
+use std::thread;
+
+// Every entry is solved on its own thread, which also checks that the days
+// don't share any state between inputs
+#[test]
+fn every_day_gives_its_expected_answers() {
+    let mut mismatches = Vec::new();
+
+    thread::scope(|scope| {
+        let mut solving = Vec::new();
+        for day in aoc::DAYS {
+            let dir = manifest::day_dir(day.number);
+            let entries = manifest::load(&dir).unwrap_or_else(|e| {
+                panic!("day {}: can't read {}: {}", day.number, manifest::FILE_NAME, e)
+            });
+            assert!(!entries.is_empty(), "day {} has no expected answers", day.number);
+
+            for entry in entries {
+                let dir = dir.clone();
+                solving.push(scope.spawn(move || {
+                    let answer = entry.solve(day, &dir).unwrap_or_else(|e| {
+                        panic!("day {} line {}: {}", day.number, entry.line, e)
+                    });
+                    (day, entry, answer)
+                }));
+            }
+        }
+
+        for handle in solving {
+            let (day, entry, answer) = handle.join().expect("solver panicked");
+            if answer != entry.expected {
+                mismatches.push(format!("day {} part {} ({}): expected {}, got {}",
+                                        day.number, entry.part, entry.args.join(" "),
+                                        entry.expected, answer));
+            }
+        }
+    });
+
+    assert!(mismatches.is_empty(), "\n{}", mismatches.join("\n"));
+}
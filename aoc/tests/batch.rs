@@ -0,0 +1,68 @@
+//! Running one day over several files and checking them against the
+//! answers next to them.
+
+extern crate aoc;
+extern crate aoc_common;
+
+use std::fs;
+
+use aoc::all::Status;
+use aoc::{batch, manifest};
+use aoc_common::Params;
+
+#[test]
+fn examples_match_their_answers() {
+    let day = aoc::find(15).unwrap();
+    let dir = manifest::day_dir(15);
+    for name in ["test_0.txt", "test_2.txt", "test_3.txt"] {
+        let outcome = batch::run_file(day, &dir.join(name), &Params::default(), true);
+        assert_eq!(outcome.status, Status::Ok, "{}", name);
+    }
+
+    // Part 2 of this one isn't in the manifest
+    let outcome = batch::run_file(day, &dir.join("test_1.txt"), &Params::default(), true);
+    assert_eq!(outcome.status, Status::Unchecked);
+    assert_eq!(outcome.answers[0].as_deref(), Some("36334"));
+}
+
+#[test]
+fn wrong_answers_and_bad_files_are_reported() {
+    let day = aoc::find(1).unwrap();
+    let dir = std::env::temp_dir().join(format!("aoc_batch_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.txt"), "+1\n-1\n").unwrap();
+    fs::write(dir.join("b.txt"), "+1\nx\n").unwrap();
+    fs::write(dir.join(manifest::FILE_NAME), "1 0 a.txt\n2 5 a.txt\n").unwrap();
+
+    let outcome = batch::run_file(day, &dir.join("a.txt"), &Params::default(), true);
+    assert_eq!(outcome.status, Status::Mismatch(vec![2]));
+    let outcome = batch::run_file(day, &dir.join("a.txt"), &Params::default(), false);
+    assert_eq!(outcome.status, Status::Unchecked);
+    let outcome = batch::run_file(day, &dir.join("b.txt"), &Params::default(), true);
+    assert!(matches!(outcome.status, Status::Error(_)));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_glob_over_a_day_runs_only_its_inputs() {
+    // What the shell makes of day_15/*.txt
+    let dir = manifest::day_dir(15);
+    let mut glob : Vec<String> = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("txt".as_ref()))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    glob.sort();
+    assert!(glob.iter().any(|path| path.ends_with(manifest::FILE_NAME)));
+
+    let inputs = batch::inputs(&glob);
+    assert_eq!(inputs.len(), glob.len() - 1);
+    let day = aoc::find(15).unwrap();
+    for path in inputs {
+        let outcome = batch::run_file(day, path.as_ref(), &Params::default(), true);
+        assert!(outcome.status == Status::Ok || outcome.status == Status::Unchecked, "{}: {:?}", path, outcome.status);
+    }
+
+    let with_params = ["day_06/test.txt", "day_06/test.params", "day_06/answers.txt"].map(String::from);
+    assert_eq!(batch::inputs(&with_params), vec!["day_06/test.txt"]);
+}
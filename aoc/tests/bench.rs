@@ -0,0 +1,50 @@
+//! Baseline files and the comparison `aoc run --bench` makes with them.
+
+extern crate aoc;
+
+use std::fs;
+use std::time::Duration;
+
+use aoc::bench::{self, Baseline, Stage};
+
+#[test]
+fn a_saved_baseline_loads_back() {
+    let path = std::env::temp_dir().join(format!("aoc_baseline_{}.txt", std::process::id()));
+    let mut baseline = Baseline::default();
+    baseline.set(7, Stage::Part(2), Duration::from_micros(1500));
+    baseline.set(7, Stage::Parse, Duration::from_nanos(800));
+    baseline.set(15, Stage::Part(1), Duration::from_millis(40));
+    baseline.save(&path).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "7 parse 800\n7 part2 1500000\n15 part1 40000000\n");
+    let loaded = Baseline::load(&path).unwrap();
+    assert_eq!(loaded.get(7, Stage::Parse), Some(Duration::from_nanos(800)));
+    assert_eq!(loaded.get(7, Stage::Part(2)), Some(Duration::from_micros(1500)));
+    assert_eq!(loaded.get(15, Stage::Part(1)), Some(Duration::from_millis(40)));
+    assert_eq!(loaded.get(7, Stage::Part(1)), None);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_missing_baseline_is_empty_and_a_bad_one_is_refused() {
+    let path = std::env::temp_dir().join(format!("aoc_baseline_bad_{}.txt", std::process::id()));
+    assert_eq!(Baseline::load(&path).unwrap().get(1, Stage::Parse), None);
+
+    fs::write(&path, "1 parse 100\n\n1 part3 x\n").unwrap();
+    let error = Baseline::load(&path).err().unwrap();
+    assert!(error.to_string().ends_with(":3: expected <day> <stage> <nanoseconds>"), "{}", error);
+    fs::write(&path, "1 stage 100\n").unwrap();
+    assert!(Baseline::load(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn only_a_clear_slowdown_is_a_regression() {
+    let before = Duration::from_millis(100);
+    assert!(!bench::regressed(Duration::from_millis(50), before));
+    assert!(!bench::regressed(Duration::from_millis(125), before));
+    assert!(bench::regressed(Duration::from_millis(126), before));
+    assert!((bench::ratio(Duration::from_millis(150), before) - 1.5).abs() < 1e-9);
+    // A baseline of nothing doesn't divide by zero
+    assert!(bench::regressed(Duration::from_millis(1), Duration::from_nanos(0)));
+}
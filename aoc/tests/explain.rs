@@ -0,0 +1,56 @@
+//! The facts behind answers, as `--explain` shows them.
+
+extern crate aoc;
+extern crate aoc_common;
+
+use aoc::manifest;
+use aoc_common::Json;
+
+// The explanation and facts for one part of a day's answer
+fn explain_input(day: u32, input: &str, part: u32) -> (Vec<String>, Json) {
+    let day = aoc::find(day).unwrap();
+    let parsed = day.solver.parse_input(input).unwrap();
+    let answer = day.solver.answer(&*parsed, part);
+    let facts = Json::object(answer.facts());
+    (answer.explain(), facts)
+}
+
+fn explain(day: u32, file: &str, part: u32) -> (Vec<String>, Json) {
+    explain_input(day, &std::fs::read_to_string(manifest::day_dir(day).join(file)).unwrap(), part)
+}
+
+#[test]
+fn days_explain_their_answers() {
+    let (lines, facts) = explain(3, "test.txt", 1);
+    assert_eq!(lines, ["2 of the 3 claims overlap another", "4 square inches are inside two or more claims"]);
+    assert_eq!(facts.to_string(), r#"{"squares":4,"claims":3,"overlapping_claims":2}"#);
+    let (lines, _) = explain(3, "test.txt", 2);
+    assert_eq!(lines, ["claim #3 @ 5,5: 2x2 overlaps no other claim"]);
+
+    let (lines, _) = explain(4, "test.txt", 1);
+    assert_eq!(lines.last().unwrap(), "10 × 24 = 240");
+    assert!(lines[0].starts_with("guard #10 slept the most, 50 minutes in all"), "{}", lines[0]);
+    let (lines, _) = explain_input(4, "[1518-11-01 00:00] Guard #2147483647 begins shift\n\
+                                       [1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up\n", 1);
+    assert_eq!(lines.last().unwrap(), "2147483647 × 5 = 10737418235");
+
+    let (lines, facts) = explain_input(6, "1, 1\n5, 5\n", 1);
+    assert_eq!(lines, ["every area reaches the edge, so none is finite"]);
+    assert_eq!(facts.to_string(), r#"{"finite_areas":0}"#);
+
+    let (lines, facts) = explain(12, "test.txt", 2);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].ends_with("= 50000000501"), "{}", lines[1]);
+    assert!(facts.to_string().contains(r#""delta":"#), "{}", facts);
+
+    let (lines, facts) = explain(15, "test.txt", 1);
+    assert_eq!(lines, ["elves attack with 3", "round 19: the elf at 4,3 died", "18 full rounds × 1546 hit points left = 27828"]);
+    assert!(facts.to_string().ends_with(r#""casualties":[{"round":19,"unit":"E","x":4,"y":3}]}"#), "{}", facts);
+}
+
+// Days without an explanation of their own fall back on their facts
+#[test]
+fn facts_stand_in_for_an_explanation() {
+    let (lines, _) = explain_input(11, "18", 2);
+    assert_eq!(lines, ["x: 90", "y: 269", "size: 16"]);
+}
@@ -0,0 +1,97 @@
+//! What the fuzz targets check, run on stable over the corpus seeds and
+//! mangled copies of them.
+
+extern crate aoc;
+extern crate aoc_common;
+
+use std::fs;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+
+use aoc::fuzz;
+use aoc_common::Rng;
+
+// How many mangled copies of each seed are tried
+const MUTATIONS : u64 = 300;
+
+// Bytes that mean something to one day or another
+const INTERESTING : &[u8] = b"0123456789 -+,:@#x<>=[]()|/\\.^v<>EG\n";
+
+// Numbers at and just past the edges of what parsers read them into
+const NUMBERS : &[&[u8]] = &[b"0", b"-1", b"255", b"65536", b"2147483648", b"4294967295", b"18446744073709551616"];
+
+// A copy of `seed` with a few random edits: truncated, a line dropped or
+// repeated, a byte changed, inserted or removed, or a number swapped for
+// an awkward one
+fn mangle(rng: &mut Rng, seed: &[u8]) -> Vec<u8> {
+    let mut data = seed.to_vec();
+    for _ in 0..rng.between(1, 4) {
+        let at = rng.below(data.len() + 1);
+        match rng.below(8) {
+            0 => data.truncate(at),
+            1 | 2 => data.insert(at, *rng.pick(INTERESTING)),
+            3 if at < data.len() => data[at] = *rng.pick(INTERESTING),
+            4 if at < data.len() => { data.remove(at); },
+            5 | 6 => {
+                let digit = |b: &u8| b.is_ascii_digit();
+                let start = at - data[..at].iter().rev().take_while(|b| digit(b)).count();
+                let end = at + data[at..].iter().take_while(|b| digit(b)).count();
+                data.splice(start..end, rng.pick(NUMBERS).iter().copied());
+            },
+            _ => {
+                let lines : Vec<&[u8]> = data.split(|&b| b == b'\n').collect();
+                let line = rng.below(lines.len());
+                let mut edited : Vec<&[u8]> = lines.clone();
+                if rng.chance(0.5) { edited.remove(line); } else { edited.insert(line, lines[line]); }
+                data = edited.join(&b'\n');
+            }
+        }
+    }
+    data
+}
+
+#[test]
+fn parsers_never_panic_on_mangled_seeds() {
+    for day in aoc::DAYS {
+        for (name, text) in fuzz::seeds(day).unwrap() {
+            fuzz::parse(day, text.as_bytes());
+            for seed in 0..MUTATIONS {
+                let data = mangle(&mut Rng::new(seed), text.as_bytes());
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| fuzz::parse(day, &data)));
+                assert!(result.is_ok(), "day {}: {} mangled with seed {} panics:\n{}",
+                        day.number, name, seed, String::from_utf8_lossy(&data));
+            }
+        }
+    }
+}
+
+#[test]
+fn the_corpus_is_seeded_from_the_test_files() {
+    let dir = std::env::temp_dir().join(format!("aoc_corpus_{}", std::process::id()));
+    fuzz::seed_corpus(&dir).unwrap();
+    let names = |day: &str| {
+        let mut names : Vec<String> = fs::read_dir(dir.join(day)).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names("day_15"), ["test.txt", "test_0.txt", "test_1.txt", "test_2.txt", "test_3.txt"]);
+    assert_eq!(names("day_09"), ["10_1618", "30_5807", "9_25"]);
+    // Nothing smaller is checked in for day 1
+    assert_eq!(names("day_01"), ["input.txt"]);
+    assert_eq!(fs::read_to_string(dir.join("day_03").join("test.txt")).unwrap(),
+               fs::read_to_string(aoc::manifest::day_dir(3).join("test.txt")).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn every_day_has_a_fuzz_target() {
+    let fuzz = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("fuzz");
+    let manifest = fs::read_to_string(fuzz.join("Cargo.toml")).unwrap();
+    for day in aoc::DAYS {
+        let name = format!("day_{:02}", day.number);
+        assert!(manifest.contains(&format!("name = \"{}\"", name)), "fuzz/Cargo.toml has no {}", name);
+        let target = fs::read_to_string(fuzz.join("fuzz_targets").join(format!("{}.rs", name))).unwrap();
+        assert!(target.contains(&format!("aoc::find({})", day.number)), "{}", target);
+    }
+}
@@ -0,0 +1,76 @@
+//! The map grid the cave and track puzzles are read into.
+
+extern crate aoc_common;
+
+use aoc_common::Grid;
+
+const MAP : &str = "#..\n.#.\n..#\n#.#";
+
+fn map() -> Grid<char> {
+    Grid::parse(MAP, |_, c| Ok(c)).unwrap()
+}
+
+#[test]
+fn cells_outside_the_grid_are_missing() {
+    let grid = map();
+    assert_eq!((grid.width(), grid.height()), (3, 4));
+    assert_eq!(grid.get((0, 0)), Some(&'#'));
+    assert_eq!(grid.get((2, 3)), Some(&'#'));
+    assert_eq!(grid.get((1, 3)), Some(&'.'));
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.get((0, 4)), None);
+    assert_eq!(grid.get((usize::MAX, usize::MAX)), None);
+    assert!(grid.contains((2, 3)) && !grid.contains((3, 3)));
+}
+
+#[test]
+fn neighbours_come_in_reading_order_and_stop_at_the_edge() {
+    let grid = map();
+    let four = |pos| grid.neighbors4(pos).collect::<Vec<_>>();
+    let eight = |pos| grid.neighbors8(pos).collect::<Vec<_>>();
+
+    assert_eq!(four((1, 1)), vec![(1, 0), (0, 1), (2, 1), (1, 2)]);
+    assert_eq!(four((0, 0)), vec![(1, 0), (0, 1)]);
+    assert_eq!(four((2, 3)), vec![(2, 2), (1, 3)]);
+    assert_eq!(four((0, 2)), vec![(0, 1), (1, 2), (0, 3)]);
+
+    assert_eq!(eight((1, 1)), vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    assert_eq!(eight((0, 0)), vec![(1, 0), (0, 1), (1, 1)]);
+    assert_eq!(eight((2, 0)), vec![(1, 0), (1, 1), (2, 1)]);
+    assert_eq!(eight((0, 3)), vec![(0, 2), (1, 2), (1, 3)]);
+    assert_eq!(eight((2, 3)), vec![(1, 2), (2, 2), (1, 3)]);
+}
+
+#[test]
+fn cells_are_visited_in_reading_order() {
+    let grid = Grid::from_fn(3, 2, |(x, y)| y * 10 + x);
+    let cells : Vec<_> = grid.iter().map(|(pos, &cell)| (pos, cell)).collect();
+    assert_eq!(cells, vec![((0, 0), 0), ((1, 0), 1), ((2, 0), 2),
+                           ((0, 1), 10), ((1, 1), 11), ((2, 1), 12)]);
+    assert_eq!(grid.positions().collect::<Vec<_>>(), cells.iter().map(|&(pos, _)| pos).collect::<Vec<_>>());
+    assert_eq!(grid.row(1), &[10, 11, 12]);
+}
+
+#[test]
+fn a_parsed_map_displays_as_it_was_read() {
+    assert_eq!(map().to_string(), MAP);
+    let trimmed = Grid::parse(" ab \n cd \n", |_, c| Ok(c)).unwrap();
+    assert_eq!(trimmed.to_string(), " ab \n cd ");
+}
+
+#[test]
+fn ragged_maps_are_refused() {
+    let short = Grid::parse("###\n#.\n###\n", |_, c| Ok(c)).unwrap_err();
+    assert_eq!((short.line, short.col), (2, 3));
+    assert_eq!(short.message, "expected 3 characters, as on line 1, found 2");
+
+    let long = Grid::parse("###\n#..#\n###\n", |_, c| Ok(c)).unwrap_err();
+    assert_eq!((long.line, long.col), (2, 4));
+}
+
+#[test]
+fn cell_errors_point_at_their_character() {
+    let error = Grid::parse("...\n.x.\n", |_, c| if c == '.' { Ok(c) } else { Err(format!("unexpected {:?}", c)) })
+        .unwrap_err();
+    assert_eq!((error.line, error.col, error.message.as_str()), (2, 2, "unexpected 'x'"));
+}
@@ -0,0 +1,51 @@
+//! The JSON written by `--format json`.
+
+extern crate aoc_common;
+
+use aoc_common::Json;
+
+fn string(s: &str) -> String {
+    Json::from(s).to_string()
+}
+
+#[test]
+fn quotes_and_backslashes_are_escaped() {
+    assert_eq!(string(r#"say "hi""#), r#""say \"hi\"""#);
+    assert_eq!(string(r"C:\aoc"), r#""C:\\aoc""#);
+    assert_eq!(string(""), r#""""#);
+}
+
+#[test]
+fn control_characters_are_escaped() {
+    assert_eq!(string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+    assert_eq!(string("\u{0}\u{1b}[0m\u{1f}"), r#""\u0000\u001b[0m\u001f""#);
+    // DEL isn't a control character as far as JSON is concerned
+    assert_eq!(string("\u{7f}"), "\"\u{7f}\"");
+}
+
+#[test]
+fn non_ascii_is_written_as_it_is() {
+    assert_eq!(string("µs × 2 → ✓"), "\"µs × 2 → ✓\"");
+    assert_eq!(string("🎄"), "\"🎄\"");
+}
+
+#[test]
+fn field_names_are_escaped_too() {
+    let object = Json::Object(vec![("a\"b".to_string(), Json::Null), ("\n".to_string(), 1.into())]);
+    assert_eq!(object.to_string(), r#"{"a\"b":null,"\n":1}"#);
+}
+
+#[test]
+fn numbers_that_json_cannot_hold_are_null() {
+    assert_eq!(Json::from(f64::NAN).to_string(), "null");
+    assert_eq!(Json::from(f64::INFINITY).to_string(), "null");
+    assert_eq!(Json::from(f64::NEG_INFINITY).to_string(), "null");
+    assert_eq!(Json::Array(vec![1.5.into(), f64::NAN.into(), (-0.25).into()]).to_string(), "[1.5,null,-0.25]");
+}
+
+#[test]
+fn values_nest() {
+    let report = Json::object(vec![("day", 7.into()), ("answer", "CABDFE".into()),
+                                   ("facts", Json::Array(vec![true.into(), None::<u32>.into()]))]);
+    assert_eq!(report.to_string(), r#"{"day":7,"answer":"CABDFE","facts":[true,null]}"#);
+}
@@ -0,0 +1,85 @@
+//! Linting finds every problem in an input, each where it is.
+
+extern crate aoc;
+
+// The line and column of each problem lint finds in `text` for a day
+fn problems(day: u32, text: &str) -> Vec<(usize, usize)> {
+    aoc::find(day).unwrap().solver.lint_input(text).iter().map(|e| (e.line, e.col)).collect()
+}
+
+#[test]
+fn every_bad_line_is_reported() {
+    assert_eq!(problems(3, "#1 @ 1,3: 4x4\n#2 @ 3,1 4x4\n#1 @ 5,5: 2x2\n#3 @ 5,5: 2x\n"),
+               vec![(2, 1), (3, 1), (4, 1)]);
+    // Claims have to fit on a fabric that fits in memory
+    assert_eq!(problems(3, "#1 @ 1,3: 4x4\n#2 @ 2147483647,1: 2147483647x4\n#3 @ 5,3999: 2x2\n"),
+               vec![(2, 20), (3, 16)]);
+    assert_eq!(problems(4, "[1518-11-01 00:00] Guard #10 begins shift\n[1518-13-01 00:05] falls asleep\n\
+                            [1518-11-01 00:25] wakes up\n[1518-11-01 00:30] dozes off\n"),
+               vec![(2, 2), (4, 20)]);
+}
+
+#[test]
+fn later_problems_wait_for_the_lines_to_be_right() {
+    // Waking up before falling asleep only shows once every record reads
+    assert_eq!(problems(4, "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up\n"),
+               vec![(2, 20)]);
+    assert_eq!(problems(7, "Step A must be finished before step B can begin.\n\
+                            Step B must be finished before step A can begin.\n\
+                            Step B must be finished before step C can begin.\n"),
+               vec![(1, 1), (2, 1)]);
+    // Naps are within the midnight hour, however far off the wake up is
+    assert_eq!(problems(4, "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n\
+                            [9999-11-01 00:25] wakes up\n"),
+               vec![(3, 2)]);
+    assert_eq!(problems(4, "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 01:05] falls asleep\n\
+                            [1518-11-01 01:25] wakes up\n"),
+               vec![(2, 2)]);
+    assert_eq!(problems(4, "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n\
+                            [1518-11-01 00:05] wakes up\n"),
+               vec![(3, 2)]);
+}
+
+#[test]
+fn maps_are_rectangular_and_walled() {
+    let cave = "#######\n#E..G#\n#..x..#\n#.....G\n#######\n";
+    assert_eq!(problems(15, cave), vec![(3, 4), (2, 7), (4, 7)]);
+    assert_eq!(problems(15, "#####\n#E.E#\n#####\n").len(), 1);
+    // Walled-off units are fine as long as some elf can reach a goblin
+    assert!(aoc::find(15).unwrap().solver.parse_input("######\n#E#EG#\n######\n").is_ok());
+    assert_eq!(problems(15, "######\n#E#.G#\n######\n"), vec![(2, 2)]);
+    assert_eq!(problems(13, "/->-\\\n|   |\n\\---/\n"), vec![(4, 1)]);
+}
+
+#[test]
+fn carts_stay_on_the_track() {
+    assert_eq!(problems(13, "-->-<-\n"), vec![(1, 1), (1, 6)]);
+    assert_eq!(problems(13, "/->-\\\n|   |\n\\-+</\n  |  \n"), vec![(3, 3), (4, 3)]);
+    assert!(aoc::find(13).unwrap().solver.parse_input("/->-\\\n|   |\n\\-<-/\n").is_ok());
+}
+
+#[test]
+fn particles_have_to_come_together() {
+    assert_eq!(problems(10, ""), vec![(1, 1)]);
+    assert_eq!(problems(10, "position=< 1, 2> velocity=< 1, 1>\n"), vec![(2, 1)]);
+    assert_eq!(problems(10, "position=< 1, 2> velocity=< 1, 1>\nposition=< 5, 2> velocity=< 1, 1>\n"), vec![(3, 1)]);
+    assert_eq!(problems(10, "position=< 1, 2> velocity=< 1, 1>\nposition=< 5, x> velocity=< 1, 1>\n"), vec![(2, 15)]);
+    assert_eq!(problems(10, "position=< 1, 2> velocity=< 1, 1>\nposition=< -2147483648, 2> velocity=< 1, 101>\n"),
+               vec![(2, 12)]);
+    assert_eq!(problems(10, "position=< 1, 2> velocity=< 1, 1>\nposition=< 5, 2> velocity=< 1, 101>\n"), vec![(2, 32)]);
+}
+
+#[test]
+fn marble_games_fit_in_memory() {
+    assert_eq!(problems(9, "0 25"), vec![(1, 1)]);
+    assert_eq!(problems(9, "100000000000000 1"), vec![(1, 1)]);
+    assert_eq!(problems(9, "10 4294967295"), vec![(1, 4)]);
+    assert!(problems(9, "1000000 25").is_empty());
+}
+
+#[test]
+fn days_without_their_own_lint_report_what_parsing_finds() {
+    assert_eq!(problems(1, "+1\n+x\n-2\n"), vec![(2, 1)]);
+    assert!(problems(1, "+1\n-2\n").is_empty());
+    assert_eq!(problems(6, ""), vec![(1, 1)]);
+}
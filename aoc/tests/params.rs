@@ -0,0 +1,65 @@
+//! Overriding a day's parameters.
+
+extern crate aoc;
+extern crate aoc_common;
+
+use aoc::manifest;
+use aoc_common::{params, Params};
+
+#[test]
+fn later_values_win_and_unknown_names_are_refused() {
+    let day = aoc::find(7).unwrap();
+    let workers = &day.solver.params()[0];
+
+    let mut set = Params::default();
+    let rest = params::configure(vec!["--set", "workers=2", "test.txt", "--set", "workers = 3"]
+                                 .into_iter().map(String::from), &mut set).unwrap();
+    assert_eq!(rest, vec!["test.txt"]);
+    assert_eq!(set.get(workers), 3);
+    assert_eq!(Params::default().get(workers), workers.default);
+    assert!(set.check(7, day.solver.params()).is_ok());
+
+    set.set("elves", 1);
+    assert!(set.check(7, day.solver.params()).is_err());
+    assert!(Params::default().assign("workers").is_err());
+    assert!(Params::default().assign("workers=two").is_err());
+}
+
+#[test]
+fn params_files_sit_next_to_their_input() {
+    let day = aoc::find(6).unwrap();
+    let dir = manifest::day_dir(6);
+    let (_, params) = manifest::load_input(day, &dir, &["test.txt".to_string()]).unwrap();
+    assert_eq!(params.get(&day.solver.params()[0]), 32);
+
+    let args = ["--set", "max_dist=50", "test.txt"].iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+    let (_, params) = manifest::load_input(day, &dir, &args).unwrap();
+    assert_eq!(params.get(&day.solver.params()[0]), 50);
+}
+
+#[test]
+fn values_out_of_range_are_refused() {
+    let set = |name: &str, value: i64| {
+        let mut params = Params::default();
+        params.set(name, value);
+        params
+    };
+    let check = |day: u32, params: &Params| params.check(day, aoc::find(day).unwrap().solver.params());
+
+    assert!(check(7, &set("workers", 1)).is_ok());
+    assert_eq!(check(7, &set("workers", 0)), Err("day 7's workers must be from 1 to 26, found 0".to_string()));
+    assert!(check(7, &set("workers", -1)).is_err());
+    assert!(check(7, &set("offset", -1)).is_err());
+    assert!(check(12, &set("part2_steps", -5)).is_err());
+    assert!(check(12, &set("part2_steps", 0)).is_ok());
+    assert!(check(15, &set("attack", 0)).is_err());
+    assert!(check(15, &set("hit_points", 0)).is_err());
+    assert!(check(6, &set("max_dist", 1_000_000_000)).is_err());
+
+    // Parsing refuses them too, for callers that don't check first
+    let day = aoc::find(15).unwrap();
+    let error = day.solver.parse_input_with("#####\n#E.G#\n#####\n", &set("attack", 0)).err().unwrap();
+    assert_eq!(error.message, "attack must be from 1 to 10000, found 0");
+    let day = aoc::find(7).unwrap();
+    assert!(day.solver.parse_input_with("Step A must be finished before step B can begin.\n", &set("workers", -1)).is_err());
+}
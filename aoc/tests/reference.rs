@@ -0,0 +1,115 @@
+//! The faster implementations checked against the obviously correct ones
+//! kept in each day's `reference` module, on made-up inputs.
+
+extern crate aoc_common;
+extern crate day_02;
+extern crate day_05;
+extern crate day_06;
+extern crate day_11;
+
+use aoc_common::property::{self, same, shrink_vec};
+use aoc_common::{Grid, Rng, Solution};
+
+const CASES : u64 = 100;
+
+// A made-up input for `solution`, parsed, of a random size up to `max_size`
+fn generated<S: Solution>(solution: &S, rng: &mut Rng, max_size: usize) -> S::Input {
+    let size = 1 + rng.below(max_size);
+    let text = solution.generate(rng, size);
+    solution.parse(&text).unwrap_or_else(|e| panic!("{}:{}: {}\n{}", e.line, e.col, e.message, text))
+}
+
+#[test]
+fn shrinking_finds_a_smallest_failure() {
+    let failure = property::find_failure(CASES, |rng| {
+        (0..20).map(|_| rng.below(50)).collect::<Vec<usize>>()
+    }, |items| shrink_vec(items, 0), |items| {
+        if items.iter().sum::<usize>() < 100 { Ok(()) } else { Err("too big".to_string()) }
+    }).expect("some case adds up to 100");
+
+    let total : usize = failure.case.iter().sum();
+    assert!(total >= 100);
+    assert!(failure.case.iter().all(|item| total - item < 100), "{:?} could shrink further", failure.case);
+}
+
+#[test]
+fn day_02_find_twins() {
+    property::check(CASES, |rng| generated(&day_02::Day02, rng, 30), |ids| {
+        let mut smaller = shrink_vec(ids, 0);
+        if ids.iter().all(|id| id.len() > 1) {
+            smaller.push(ids.iter().map(|id| id[1..].to_string()).collect());
+        }
+        smaller
+    }, |ids| same(day_02::reference::find_twins(ids), day_02::find_twins(ids)));
+}
+
+#[test]
+fn day_05_collapse() {
+    property::check(CASES, |rng| generated(&day_05::Day05, rng, 200), |polymer| shrink_vec(polymer, 0), |polymer| {
+        same(day_05::reference::collapse(polymer), day_05::collapse(polymer))
+    });
+}
+
+#[test]
+fn day_06_closest() {
+    property::check(CASES, |rng| generated(&day_06::Day06, rng, 12).0, |coords| shrink_vec(coords, 2), |coords| {
+        let min_x = coords.iter().map(|&(x, _)| x).min().unwrap() - 2;
+        let max_x = coords.iter().map(|&(x, _)| x).max().unwrap() + 2;
+        let min_y = coords.iter().map(|&(_, y)| y).min().unwrap() - 2;
+        let max_y = coords.iter().map(|&(_, y)| y).max().unwrap() + 2;
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                same(day_06::reference::closest(coords, (x, y)), day_06::closest(coords, (x, y)))
+                    .map_err(|e| format!("at {},{}: {}", x, y, e))?;
+            }
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn day_06_region_size() {
+    property::check(CASES, |rng| {
+        (generated(&day_06::Day06, rng, 12).0, rng.between(1, 40) as i32)
+    }, |(coords, max_dist)| {
+        shrink_vec(coords, 1).into_iter().map(|coords| (coords, *max_dist)).collect()
+    }, |&(ref coords, max_dist)| {
+        same(day_06::reference::region_size(coords, max_dist), day_06::region_size(coords, max_dist))
+    });
+}
+
+#[test]
+fn day_11_square_power() {
+    use day_11::{Cell, Sums};
+
+    property::check(CASES, |rng| {
+        let side = 1 + rng.below(12);
+        Grid::from_fn(side, side, |_| rng.between(-5, 4) as i8)
+    }, |levels| {
+        let side = levels.width();
+        let mut smaller = Vec::new();
+        if side > 1 {
+            smaller.push(Grid::from_fn(side - 1, side - 1, |pos| levels[pos]));
+        }
+        for (pos, &level) in levels.iter() {
+            if level != 0 {
+                let mut zeroed = levels.clone();
+                zeroed[pos] = 0;
+                smaller.push(zeroed);
+            }
+        }
+        smaller
+    }, |levels| {
+        let sums = Sums::new(levels);
+        let side = levels.width() as u32;
+        for size in 1..=side {
+            for y in 1..=(side + 1 - size) {
+                for x in 1..=(side + 1 - size) {
+                    same(day_11::reference::square_power(levels, Cell(x, y), size), sums.square_power(Cell(x, y), size))
+                        .map_err(|e| format!("at {},{} size {}: {}", x, y, size, e))?;
+                }
+            }
+        }
+        Ok(())
+    });
+}
@@ -0,0 +1,64 @@
+//! Starting new days in a copy of the workspace's registry files.
+
+extern crate aoc;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc::scaffold;
+
+// A workspace holding copies of the files a new day is registered in
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc_scaffold_{}_{}", name, std::process::id()));
+    let real = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+    fs::create_dir_all(root.join("fuzz")).unwrap();
+    for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/lib.rs", "fuzz/Cargo.toml"] {
+        fs::copy(real.join(file), root.join(file)).unwrap();
+    }
+    root
+}
+
+// The lines of a file that mention `text`
+fn lines_with(path: &Path, text: &str) -> Vec<String> {
+    fs::read_to_string(path).unwrap().lines().filter(|line| line.contains(text)).map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn new_days_are_registered_in_order() {
+    let root = workspace("order");
+    scaffold::create(&root, 18).unwrap();
+    scaffold::create(&root, 16).unwrap();
+
+    let lib = fs::read_to_string(root.join("day_16").join("src").join("lib.rs")).unwrap();
+    assert!(lib.contains("pub struct Day16;") && lib.contains("const DAY : u32 = 16;"), "{}", lib);
+    assert!(fs::read_to_string(root.join("day_16").join("answers.txt")).unwrap().contains("1 0 test.txt"));
+    assert_eq!(fs::read_to_string(root.join("day_16").join("input.txt")).unwrap(), "");
+
+    assert_eq!(lines_with(&root.join("aoc").join("src").join("lib.rs"), "day!(day_1"),
+               ["    day!(day_10::Day10)", "    day!(day_11::Day11)", "    day!(day_12::Day12)",
+                "    day!(day_13::Day13)", "    day!(day_14::Day14)", "    day!(day_15::Day15)",
+                "    day!(day_16::Day16)", "    day!(day_18::Day18)"].map(|line| format!("{},", line)));
+    assert_eq!(lines_with(&root.join("aoc").join("Cargo.toml"), "day_1").last().unwrap(),
+               r#"day_18 = { path = "../day_18" }"#);
+    assert!(lines_with(&root.join("Cargo.toml"), "\"day_16\"")[0].ends_with(r#"    "day_16", "day_18","#));
+
+    let target = fs::read_to_string(root.join("fuzz").join("fuzz_targets").join("day_16.rs")).unwrap();
+    assert!(target.contains("aoc::find(16)"), "{}", target);
+    assert_eq!(lines_with(&root.join("fuzz").join("Cargo.toml"), "fuzz_targets/"),
+               (1..=18).filter(|&day| day != 17).map(|day| format!("path = \"fuzz_targets/day_{:02}.rs\"", day))
+                   .collect::<Vec<String>>());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn existing_days_are_left_alone() {
+    let root = workspace("existing");
+    let before = fs::read_to_string(root.join("aoc").join("src").join("lib.rs")).unwrap();
+    assert_eq!(scaffold::create(&root, 15).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(scaffold::create(&root, 26).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(fs::read_to_string(root.join("aoc").join("src").join("lib.rs")).unwrap(), before);
+    assert!(!root.join("day_15").exists());
+    fs::remove_dir_all(&root).unwrap();
+}
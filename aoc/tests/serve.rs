@@ -0,0 +1,108 @@
+//! Solving over HTTP, with a client on the loopback interface.
+
+extern crate aoc;
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+// A server on a port of its own, left running until the test ends
+fn start() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || aoc::serve::serve(listener));
+    addr
+}
+
+// The status and body of the reply to a request
+fn request(addr: SocketAddr, method: &str, target: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+           method, target, body.len(), body).unwrap();
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).unwrap();
+
+    let (head, body) = reply.split_once("\r\n\r\n").expect("a reply with headers and a body");
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+#[test]
+fn answers_and_diagnostics() {
+    let addr = start();
+
+    let (status, body) = request(addr, "POST", "/day/1/part/2", "+3\n+3\n+4\n-2\n-4\n");
+    assert_eq!(status, 200);
+    assert!(body.starts_with(r#"{"day":1,"part":2,"answer":"10","elapsed_ms":"#), "{}", body);
+
+    let (status, body) = request(addr, "POST", "/day/1/part/1", "+1\n+x\n");
+    assert_eq!(status, 422);
+    assert!(body.contains(r#""line":2,"col":1"#), "{}", body);
+
+    // Query pairs set parameters, and values days take their values as the body
+    let steps = "Step C must be finished before step A can begin.\n\
+                 Step C must be finished before step F can begin.\n\
+                 Step A must be finished before step B can begin.\n\
+                 Step A must be finished before step D can begin.\n\
+                 Step B must be finished before step E can begin.\n\
+                 Step D must be finished before step E can begin.\n\
+                 Step F must be finished before step E can begin.\n";
+    let (status, body) = request(addr, "POST", "/day/7/part/2?workers=2&offset=0", steps);
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""answer":"15""#), "{}", body);
+    let (status, body) = request(addr, "POST", "/day/9/part/1", "10 1618\n");
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""answer":"8317""#), "{}", body);
+}
+
+#[test]
+fn inputs_that_would_never_finish_get_an_answer() {
+    let addr = start();
+
+    let cycle = "Step A must be finished before step B can begin.\n\
+                 Step B must be finished before step A can begin.\n";
+    let (status, body) = request(addr, "POST", "/day/7/part/2", cycle);
+    assert_eq!(status, 422);
+    assert!(body.contains(r#""line":1,"col":1"#), "{}", body);
+    assert!(body.contains("cycle of requirements"), "{}", body);
+
+    let (status, body) = request(addr, "POST", "/day/15/part/1", "#######\n#E.#.G#\n#######\n");
+    assert_eq!(status, 422);
+    assert!(body.contains(r#""line":2,"col":2"#), "{}", body);
+    // One elf the goblins kill however hard it hits
+    let (status, body) = request(addr, "POST", "/day/15/part/2?hit_points=4", "#####\n#GGG#\n#GEG#\n#GGG#\n#####\n");
+    assert_eq!(status, 500);
+    assert!(body.contains("no attack power saves every elf"), "{}", body);
+    let (status, body) = request(addr, "POST", "/day/13/part/1", "/->-\\\n|   |\n\\-<-/\n");
+    assert_eq!(status, 500);
+    assert!(body.contains("without crashing"), "{}", body);
+    // The first crash is the answer to part 1 even if the other carts never crash
+    let loops = "/>-<\\\n|   |\n\\---/\n/>->\\\n|   |\n\\---/\n";
+    let (status, body) = request(addr, "POST", "/day/13/part/1", loops);
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""answer":"2,0""#), "{}", body);
+    assert_eq!(request(addr, "POST", "/day/13/part/2", loops).0, 500);
+
+    // The server is still free to answer
+    assert_eq!(request(addr, "POST", "/day/1/part/1", "+1\n+2\n").0, 200);
+}
+
+#[test]
+fn answers_too_big_to_work_out_get_a_500() {
+    let addr = start();
+    let pots = std::fs::read_to_string(aoc::manifest::day_dir(12).join("input.txt")).unwrap();
+    let (status, body) = request(addr, "POST", "/day/12/part/2?part2_steps=9000000000000000000", &pots);
+    assert_eq!(status, 500);
+    assert!(body.contains("too big for an i64"), "{}", body);
+    assert_eq!(request(addr, "POST", "/day/12/part/2?part2_steps=1000000000000", &pots).0, 200);
+}
+
+#[test]
+fn bad_requests_are_refused() {
+    let addr = start();
+    assert_eq!(request(addr, "POST", "/day/99/part/1", "").0, 404);
+    assert_eq!(request(addr, "POST", "/day/1/part/3", "").0, 404);
+    assert_eq!(request(addr, "POST", "/days", "").0, 404);
+    assert_eq!(request(addr, "GET", "/day/1/part/1", "").0, 405);
+    assert_eq!(request(addr, "POST", "/day/1/part/1?workers=2", "+1\n").0, 400);
+}
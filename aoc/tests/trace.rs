@@ -0,0 +1,25 @@
+//! Switching trace points on from the command line.
+
+extern crate aoc_common;
+
+use aoc_common::trace::{self, Level};
+
+// The switches are global, so everything is checked in one test
+#[test]
+fn flags_switch_on_targets_and_levels() {
+    let args = vec!["--day", "15", "--trace", "day_15::moves", "day_15/test.txt"];
+    let rest = trace::configure(args.into_iter().map(String::from)).unwrap();
+    assert_eq!(rest, vec!["--day", "15", "day_15/test.txt"]);
+
+    assert!(trace::enabled(Level::Trace, "day_15::moves"));
+    assert!(trace::enabled(Level::Trace, "day_15::moves::search"));
+    assert!(!trace::enabled(Level::Trace, "day_15::movesets"));
+    assert!(!trace::enabled(Level::Verbose, "day_15"));
+    assert!(!trace::enabled(Level::Verbose, "day_01"));
+
+    trace::configure(vec!["-v".to_string()]).unwrap();
+    assert!(trace::enabled(Level::Verbose, "day_01"));
+    assert!(!trace::enabled(Level::Trace, "day_01"));
+
+    assert!(trace::configure(vec!["--trace".to_string()]).is_err());
+}
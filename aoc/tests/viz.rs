@@ -0,0 +1,152 @@
+//! What the visualizations write to disk.
+
+extern crate aoc_common;
+extern crate png;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_common::{Grid, ImageFormat};
+use aoc_common::viz::{self, Backend, Frame, FrameWriter, Headless, Kind, Recorder};
+
+fn scratch(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("aoc_viz_{}_{}", name, std::process::id()))
+}
+
+fn frame(map: &str) -> Frame {
+    Frame::new(Grid::parse(map, |_, c| Ok(c)).unwrap())
+}
+
+// The time of an asciicast event line, `[time,"o",data]`
+fn event_time(line: &str) -> f64 {
+    line[1..line.find(',').unwrap()].parse().unwrap()
+}
+
+#[test]
+fn a_recording_is_an_asciicast() {
+    let path = scratch("cast");
+    let mut recorder = Recorder::create(path.to_str().unwrap(), Box::new(Headless), Duration::from_millis(500)).unwrap();
+    recorder.draw(&frame("#..\n.#.").with_text("round 1"));
+    recorder.draw(&frame("...\n\"\\."));
+    recorder.finish();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let lines : Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+
+    // The header is sized for the first frame, text included
+    let header = lines[0].split(",\"timestamp\":").collect::<Vec<_>>();
+    assert_eq!(header[0], r#"{"version":2,"width":7,"height":3"#);
+    assert!(header[1].trim_end_matches('}').parse::<u64>().unwrap() > 0);
+
+    assert_eq!(lines[1], r#"[0,"o","\u001b[H\u001b[2J#..\r\n.#.\r\nround 1"]"#);
+    assert!(lines[2].starts_with('[') && lines[2].ends_with(r#","o","\u001b[H\u001b[2J...\r\n\"\\."]"#), "{}", lines[2]);
+    // Frames drawn straight after each other are spread out to be watched
+    assert!(event_time(lines[2]) >= 0.5);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn event_times_only_go_forward() {
+    let path = scratch("times");
+    let mut recorder = Recorder::create(path.to_str().unwrap(), Box::new(Headless), Duration::from_millis(100)).unwrap();
+    for _ in 0..5 {
+        recorder.draw(&frame("#"));
+    }
+    recorder.finish();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let times : Vec<f64> = text.lines().skip(1).map(event_time).collect();
+    assert_eq!(times.len(), 5);
+    assert_eq!(times[0], 0.0);
+    for pair in times.windows(2) {
+        assert!(pair[1] - pair[0] >= 0.1 - 1e-6, "{:?}", times);
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+fn cave() -> Frame {
+    frame("#E.\n.G#").with_palette(|c| match c {
+        '#' => Kind::Wall,
+        'E' => Kind::Elf,
+        'G' => Kind::Goblin,
+        _ => Kind::Empty
+    }).with_text("left out of images")
+}
+
+// The size and pixels of a PPM image
+fn read_ppm(path: &PathBuf) -> (usize, usize, Vec<u8>) {
+    let data = fs::read(path).unwrap();
+    let header : Vec<&[u8]> = data.splitn(5, |b| b.is_ascii_whitespace()).collect();
+    assert_eq!(header[0], b"P6");
+    assert_eq!(header[3], b"255");
+    let number = |field: &[u8]| std::str::from_utf8(field).unwrap().parse::<usize>().unwrap();
+    (number(header[1]), number(header[2]), header[4].to_vec())
+}
+
+fn read_png(path: &PathBuf) -> (usize, usize, Vec<u8>) {
+    let decoder = png::Decoder::new(fs::File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((info.color_type, info.bit_depth), (png::ColorType::Rgb, png::BitDepth::Eight));
+    pixels.truncate(info.buffer_size());
+    (info.width as usize, info.height as usize, pixels)
+}
+
+// Each cell of `cave` is a 4x4 square of its kind's color
+fn check_cave(width: usize, height: usize, pixels: &[u8]) {
+    assert_eq!((width, height), (12, 8));
+    assert_eq!(pixels.len(), width * height * 3);
+    let pixel = |x: usize, y: usize| [pixels[(y * width + x) * 3], pixels[(y * width + x) * 3 + 1], pixels[(y * width + x) * 3 + 2]];
+    for y in 0..height {
+        for x in 0..width {
+            let kind = match (x / 4, y / 4) {
+                (0, 0) | (2, 1) => Kind::Wall,
+                (1, 0) => Kind::Elf,
+                (1, 1) => Kind::Goblin,
+                _ => Kind::Empty
+            };
+            assert_eq!(pixel(x, y), kind.color(), "pixel {},{}", x, y);
+        }
+    }
+}
+
+#[test]
+fn images_are_the_picture_in_its_palette() {
+    let ppm = scratch("cave.ppm");
+    viz::write_image(&cave(), &ppm, ImageFormat::Ppm).unwrap();
+    let (width, height, pixels) = read_ppm(&ppm);
+    check_cave(width, height, &pixels);
+    fs::remove_file(&ppm).unwrap();
+
+    let png = scratch("cave.png");
+    viz::write_image(&cave(), &png, ImageFormat::Png).unwrap();
+    let (width, height, pixels) = read_png(&png);
+    check_cave(width, height, &pixels);
+    fs::remove_file(&png).unwrap();
+}
+
+#[test]
+fn every_frame_is_saved_in_order() {
+    let dir = scratch("frames");
+    let mut writer = FrameWriter::create(dir.join("out").to_str().unwrap(), ImageFormat::Png, Box::new(Headless)).unwrap();
+    writer.draw(&cave());
+    writer.draw(&frame("# #"));
+    writer.finish();
+
+    let mut names : Vec<String> = fs::read_dir(dir.join("out")).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+    names.sort();
+    assert_eq!(names, vec!["frame_00000.png", "frame_00001.png"]);
+
+    let (width, height, pixels) = read_png(&dir.join("out/frame_00000.png"));
+    check_cave(width, height, &pixels);
+    let (width, height, pixels) = read_png(&dir.join("out/frame_00001.png"));
+    assert_eq!((width, height), (12, 4));
+    // The plain palette: a space is empty and anything else is other
+    assert_eq!(&pixels[..3], &Kind::Other.color());
+    assert_eq!(&pixels[4 * 3..4 * 3 + 3], &Kind::Empty.color());
+    fs::remove_dir_all(&dir).unwrap();
+}
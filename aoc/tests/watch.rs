@@ -0,0 +1,27 @@
+//! Noticing when a watched file changes.
+
+extern crate aoc;
+
+use std::fs;
+
+use aoc::watch::Watcher;
+
+#[test]
+fn polls_report_each_change_once() {
+    let path = std::env::temp_dir().join(format!("aoc_watch_{}.txt", std::process::id()));
+    fs::write(&path, "#E.G#\n").unwrap();
+
+    let mut watcher = Watcher::new(&path);
+    assert!(watcher.poll(), "the first poll should count as a change");
+    assert!(!watcher.poll());
+
+    fs::write(&path, "#E..G#\n").unwrap();
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+
+    fs::remove_file(&path).unwrap();
+    assert!(!watcher.poll(), "a missing file hasn't changed");
+    fs::write(&path, "#E...G#\n").unwrap();
+    assert!(watcher.poll(), "a file saved by moving a new one in has changed");
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,137 @@
+//! Errors for malformed puzzle input, pointing at the line and column that
+//! couldn't be read.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use crate::Json;
+
+/// Input that a day couldn't make sense of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// Where the input came from, once known.
+    pub file: Option<String>,
+    /// Line and column of the offending text, counting from 1.
+    pub line: usize,
+    pub col: usize,
+    /// The offending text itself.
+    pub text: String,
+    pub message: String,
+    // The whole line, to show the offending text in context
+    source_line: String,
+}
+
+impl ParseError {
+    /// An error about everything that's missing after the end of `input`.
+    pub fn at_end(input: &str, message: &str) -> Self {
+        ParseError {
+            file: None,
+            line: input.lines().count() + 1,
+            col: 1,
+            text: String::new(),
+            message: message.to_string(),
+            source_line: String::new(),
+        }
+    }
+
+    /// An error about the character at `(x, y)` of a map, counting from 0
+    /// as a `Grid` does. Past the end of its line, it points just after it.
+    pub fn at(input: &str, (x, y): (usize, usize), message: &str) -> Self {
+        match lines(input).nth(y) {
+            Some(line) => {
+                let start = line.text.char_indices().nth(x).map_or(line.text.len(), |(start, _)| start);
+                let end = line.text[start..].chars().next().map_or(start, |c| start + c.len_utf8());
+                line.error_in(&line.text[start..end], message)
+            },
+            None => ParseError::at_end(input, message)
+        }
+    }
+
+    /// Records the name of the file the input was read from.
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("message", self.message.as_str().into()),
+            ("file", self.file.clone().into()),
+            ("line", self.line.into()),
+            ("col", self.col.into()),
+            ("text", self.text.as_str().into()),
+        ])
+    }
+}
+
+/// Prints a compiler-style diagnostic:
+///
+/// ```text
+/// error: expected a number
+///  --> day_01/input.txt:3:1
+///   |
+/// 3 | +1x
+///   | ^^^
+/// ```
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, self.file.as_deref().unwrap_or("<input>"), self.line, self.col)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(self.col - 1),
+               "^".repeat(std::cmp::max(1, self.text.chars().count())))
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(e: ParseError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+    }
+}
+
+/// One line of the input, kept around so errors can point into it.
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    /// Counting from 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error about `part` of this line, which must be a slice of `text`
+    /// (as handed out by `split`, `trim` or regex captures).
+    pub fn error_in(&self, part: &str, message: &str) -> ParseError {
+        let start = (part.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)
+            .filter(|start| start + part.len() <= self.text.len())
+            .expect("error_in() given text from another line");
+        ParseError {
+            file: None,
+            line: self.number,
+            col: self.text[..start].chars().count() + 1,
+            text: part.to_string(),
+            message: message.to_string(),
+            source_line: self.text.to_string(),
+        }
+    }
+
+    /// An error about the whole line.
+    pub fn error(&self, message: &str) -> ParseError {
+        self.error_in(self.text, message)
+    }
+
+    /// Parses `part` of this line, reporting it as `what` if it won't parse.
+    pub fn parse<T: FromStr>(&self, part: &'a str, what: &str) -> Result<T, ParseError> {
+        part.parse::<T>().map_err(|_| self.error_in(part, &format!("expected {}", what)))
+    }
+}
+
+/// The lines of `input`, numbered for error reporting.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().zip(1..).map(|(text, number)| Line { number, text })
+}
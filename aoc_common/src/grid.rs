@@ -0,0 +1,165 @@
+//! A rectangular grid of cells addressed by `(x, y)`, with `(0, 0)` in the
+//! top left corner, for the puzzles that come as a map of characters.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{self, Line, ParseError};
+use crate::lint;
+
+// Neighbour offsets, listed in reading order
+const NEIGHBORS_4 : &[(isize, isize)] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBORS_8 : &[(isize, isize)] = &[(-1, -1), (0, -1), (1, -1),
+                                          (-1, 0), (1, 0),
+                                          (-1, 1), (0, 1), (1, 1)];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid with each cell set to `f((x, y))`, filled in reading order.
+    pub fn from_fn<F: FnMut((usize, usize)) -> T>(width: usize, height: usize, f: F) -> Self {
+        let cells = positions(width, height).map(f).collect();
+        Grid { width, height, cells }
+    }
+
+    /// Reads a map with one row per line, turning each character into a
+    /// cell with `f((x, y), c)`. Every line must be as wide as the first.
+    /// An error message from `f` is reported against the character it was
+    /// given.
+    pub fn parse<F>(text: &str, mut f: F) -> Result<Self, ParseError>
+    where F: FnMut((usize, usize), char) -> Result<T, String>
+    {
+        if let Some(ragged) = lint::rectangular(text).into_iter().next() {
+            return Err(ragged);
+        }
+        let lines : Vec<Line> = error::lines(text).collect();
+        let width = lines.first().map_or(0, |line| line.text.chars().count());
+        let height = lines.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, line) in lines.iter().enumerate() {
+            for (x, (start, c)) in line.text.char_indices().enumerate() {
+                let end = start + c.len_utf8();
+                let cell = f((x, y), c).map_err(|message| line.error_in(&line.text[start..end], &message))?;
+                cells.push(cell);
+            }
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.offset(pos)])
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            let offset = self.offset(pos);
+            Some(&mut self.cells[offset])
+        }
+        else {
+            None
+        }
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every position in reading order: left to right, then top to bottom.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        positions(self.width, self.height)
+    }
+
+    /// Every cell along with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The up to 4 positions sharing an edge with `pos`, in reading order.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbors(pos, self.width, self.height, NEIGHBORS_4)
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `pos`, in
+    /// reading order.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbors(pos, self.width, self.height, NEIGHBORS_8)
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+}
+
+fn positions(width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+}
+
+fn neighbors((x, y): (usize, usize), width: usize, height: usize,
+             offsets: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        if x < width && y < height { Some((x, y)) } else { None }
+    })
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("({},{}) is outside the {}x{} grid", pos.0, pos.1, self.width, self.height)
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("({},{}) is outside the {}x{} grid", pos.0, pos.1, width, height)
+        }
+    }
+}
+
+/// Draws the grid one row per line, each cell with its own `Display`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
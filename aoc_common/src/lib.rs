@@ -0,0 +1,313 @@
+//! Plumbing shared by every day: command line handling, loading the
+//! puzzle input and printing answers.
+
+mod error;
+mod grid;
+mod json;
+pub mod lint;
+pub mod params;
+pub mod property;
+mod rng;
+mod solution;
+pub mod trace;
+pub mod viz;
+
+pub use error::{lines, Line, ParseError};
+pub use grid::Grid;
+pub use json::Json;
+pub use params::{Param, Params};
+pub use rng::Rng;
+pub use solution::{Answer, Solution, Solver};
+pub use viz::{ImageFormat, Viz};
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Puzzle input and where it came from, for error messages.
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+/// What a day expects to find on its command line.
+#[derive(Clone, Copy)]
+pub enum InputShape {
+    /// A single puzzle input file, read into memory.
+    File,
+    /// Literal values given on the command line. A name written as
+    /// `"[x]"` is optional.
+    Values(&'static [&'static str]),
+}
+
+impl InputShape {
+    /// The argument part of a usage line, e.g. `<input_file>`.
+    pub fn usage(&self) -> String {
+        match self {
+            InputShape::File => "<input_file>".to_string(),
+            InputShape::Values(names) => names.iter().map(|name| {
+                if is_optional(name) {
+                    format!("[<{}>]", &name[1..name.len() - 1])
+                }
+                else {
+                    format!("<{}>", name)
+                }
+            }).collect::<Vec<String>>().join(" ")
+        }
+    }
+
+    /// Turns the remaining command line arguments into the puzzle input.
+    /// Files are read whole; values are joined with single spaces.
+    /// Returns `None` when the arguments don't fit the shape.
+    pub fn load<I: Iterator<Item = String>>(&self, mut args: I) -> Option<io::Result<Input>> {
+        match self {
+            InputShape::File => {
+                let file_name = args.next()?;
+                Some(fs::read_to_string(&file_name).map(|text| Input { name: file_name, text }))
+            },
+            InputShape::Values(names) => {
+                let values : Vec<String> = args.collect();
+                let required = names.iter().filter(|name| !is_optional(name)).count();
+                if values.len() < required || values.len() > names.len() {
+                    return None;
+                }
+                Some(Ok(Input { name: "<command line>".to_string(), text: values.join(" ") }))
+            }
+        }
+    }
+}
+
+fn is_optional(name: &str) -> bool {
+    name.starts_with('[') && name.ends_with(']')
+}
+
+/// How answers and errors are written out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    /// `Part 1: 42`, and diagnostics on stderr.
+    Text,
+    /// One JSON object per line on stdout.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {} (expected text or json)", s))
+        }
+    }
+}
+
+/// Prints the answer to one part of a puzzle.
+pub fn print_answer<T: fmt::Display>(part: u32, value: T) {
+    println!("Part {}: {}", part, value);
+}
+
+/// The JSON report for one part: the day, part, answer, how long it took
+/// and any facts the day found along the way.
+pub fn answer_json(day: u32, part: u32, answer: &dyn Answer, elapsed: Duration) -> Json {
+    let facts = answer.facts().into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+    Json::object(vec![
+        ("day", day.into()),
+        ("part", part.into()),
+        ("answer", answer.to_string().into()),
+        ("elapsed_ms", (elapsed.as_micros() as f64 / 1000.0).into()),
+        ("facts", Json::Object(facts)),
+    ])
+}
+
+/// Prints the answer to one part in the chosen format. If `explain` is
+/// set, the answer's explanation follows it: indented lines of text, or
+/// an `explanation` array in JSON.
+pub fn report(format: Format, day: u32, part: u32, answer: &dyn Answer, elapsed: Duration, explain: bool) {
+    match format {
+        Format::Text => {
+            print_answer(part, answer);
+            if explain {
+                for line in answer.explain() {
+                    println!("    {}", line);
+                }
+            }
+        },
+        Format::Json => {
+            let mut json = answer_json(day, part, answer, elapsed);
+            if let (true, Json::Object(fields)) = (explain, &mut json) {
+                let lines = answer.explain().into_iter().map(Json::from).collect();
+                fields.push(("explanation".to_string(), Json::Array(lines)));
+            }
+            println!("{}", json)
+        }
+    }
+}
+
+/// Calls `f`, returning its result and how long it took.
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// What a day's binary was asked to do.
+pub struct CommandLine {
+    pub input: Input,
+    pub format: Format,
+    /// How to draw the days that can show their work.
+    pub viz: Viz,
+    /// Where to record what's drawn, from `--record`.
+    pub record: Option<String>,
+    /// Where to save each frame as an image, from `--frames-dir`, and in
+    /// what format.
+    pub frames_dir: Option<String>,
+    pub frame_format: ImageFormat,
+    /// Whether to explain how each answer was reached, from `--explain`.
+    pub explain: bool,
+    /// Values for the day's parameters, from the input's `.params` file
+    /// and `--set`.
+    pub params: Params,
+}
+
+impl CommandLine {
+    /// Whether a day that can show its work should draw anything at all.
+    pub fn draws(&self) -> bool {
+        self.viz != Viz::Headless || self.record.is_some() || self.frames_dir.is_some()
+    }
+
+    /// The backend chosen with `--viz`, recording to the `--record` file
+    /// and saving images in the `--frames-dir` directory if they were
+    /// given. Each frame is shown for `delay`.
+    pub fn backend(&self, delay: Duration) -> io::Result<Box<dyn viz::Backend>> {
+        let mut backend = self.viz.backend(delay);
+        if let Some(ref path) = self.record {
+            backend = Box::new(viz::Recorder::create(path, backend, delay)?);
+        }
+        if let Some(ref dir) = self.frames_dir {
+            backend = Box::new(viz::FrameWriter::create(dir, self.frame_format, backend)?);
+        }
+        Ok(backend)
+    }
+}
+
+/// Reads the command line of a day's binary: the input described by
+/// `shape`, an optional `--format text|json`, an optional
+/// `--viz curses|ansi|none`, an optional `--record <file>` and an optional
+/// `--frames-dir <dir>` with `--frame-format ppm|png`, and an optional
+/// `--explain`. `-v`, `-vv` and
+/// `--trace <target>` switch on trace points, as `trace` describes, and
+/// `--set <name>=<value>` sets a parameter, as `params` describes.
+/// Prints the usage line and exits if the arguments don't fit.
+pub fn command_line(shape: InputShape) -> io::Result<CommandLine> {
+    let mut args = std::env::args();
+    let prog_name = args.next().unwrap_or_else(|| "aoc".to_string());
+    let usage = || -> ! {
+        eprintln!("Usage: {} [--format text|json] [--viz curses|ansi|none] [--record <file>]", prog_name);
+        eprintln!("       [--frames-dir <dir> [--frame-format ppm|png]] [--explain] [-v|-vv] [--trace <target>]");
+        eprintln!("       [--set <name>=<value>]... {}", shape.usage());
+        process::exit(1);
+    };
+    let mut set = Params::default();
+    let mut args = trace::configure(args).and_then(|args| params::configure(args, &mut set))
+        .unwrap_or_else(|e| { eprintln!("error: {}", e); usage() }).into_iter();
+
+    let mut format = Format::Text;
+    let mut viz = None;
+    let mut record = None;
+    let mut frames_dir = None;
+    let mut frame_format = ImageFormat::Png;
+    let mut explain = false;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = match args.next().map(|value| value.parse::<Format>()) {
+                Some(Ok(format)) => format,
+                Some(Err(e)) => { eprintln!("error: {}", e); usage() },
+                None => usage()
+            };
+        }
+        else if arg == "--explain" {
+            explain = true;
+        }
+        else if arg == "--record" {
+            record = Some(args.next().unwrap_or_else(|| usage()));
+        }
+        else if arg == "--frames-dir" {
+            frames_dir = Some(args.next().unwrap_or_else(|| usage()));
+        }
+        else if arg == "--frame-format" {
+            frame_format = match args.next().map(|value| value.parse::<ImageFormat>()) {
+                Some(Ok(frame_format)) => frame_format,
+                Some(Err(e)) => { eprintln!("error: {}", e); usage() },
+                None => usage()
+            };
+        }
+        else if arg == "--viz" {
+            viz = match args.next().map(|value| value.parse::<Viz>()) {
+                Some(Ok(viz)) => Some(viz),
+                Some(Err(e)) => { eprintln!("error: {}", e); usage() },
+                None => usage()
+            };
+        }
+        else {
+            rest.push(arg);
+        }
+    }
+
+    let input = match shape.load(rest.into_iter()) {
+        Some(input) => input?,
+        None => usage()
+    };
+    let mut params = Params::for_input(&input.name)?;
+    params.extend(&set);
+    Ok(CommandLine { input, format, viz: viz.unwrap_or_else(|| Viz::default_for(format)),
+                     record, frames_dir, frame_format, explain, params })
+}
+
+/// Reports input that couldn't be parsed and exits: a diagnostic on
+/// stderr, or a JSON object with an `error` field on stdout.
+pub fn exit_with(day: u32, error: ParseError, input: &Input, format: Format) -> ! {
+    let error = error.in_file(&input.name);
+    match format {
+        Format::Text => eprintln!("{}", error),
+        Format::Json => println!("{}", Json::object(vec![
+            ("day", day.into()),
+            ("error", error.to_json()),
+        ]))
+    }
+    process::exit(1);
+}
+
+/// Parses the input for a day's binary with the parameters given for it,
+/// exiting with a diagnostic if either is malformed.
+pub fn parse<S: Solution>(solution: &S, command_line: &CommandLine) -> S::Input {
+    let input = &command_line.input;
+    if let Err(e) = command_line.params.check(S::DAY, S::PARAMS) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+    solution.parse_with(&input.text, &command_line.params)
+        .unwrap_or_else(|e| exit_with(S::DAY, e, input, command_line.format))
+}
+
+/// Solves and reports both parts as the command line asks.
+pub fn solve<S: Solution>(solution: &S, input: &S::Input, command_line: &CommandLine) {
+    let (format, explain) = (command_line.format, command_line.explain);
+    let (answer, elapsed) = timed(|| solution.part1(input));
+    report(format, S::DAY, 1, &answer, elapsed, explain);
+    let (answer, elapsed) = timed(|| solution.part2(input));
+    report(format, S::DAY, 2, &answer, elapsed, explain);
+}
+
+/// Entry point for a day's binary: solves both parts for the input named
+/// on the command line.
+pub fn run<S: Solution>(solution: &S) -> io::Result<()> {
+    let command_line = command_line(S::INPUT)?;
+    let input = parse(solution, &command_line);
+    solve(solution, &input, &command_line);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Checks on the structure of a puzzle input that find every problem in
+//! it at once, rather than stopping at the first like parsing does. Days
+//! build their `Solution::lint` out of these.
+
+use crate::error::{self, Line};
+use crate::ParseError;
+
+/// Runs `check` on every line, keeping each error it finds.
+pub fn each_line<'a, F>(input: &'a str, mut check: F) -> Vec<ParseError>
+where F: FnMut(Line<'a>) -> Result<(), ParseError>
+{
+    error::lines(input).filter_map(|line| check(line).err()).collect()
+}
+
+/// Runs `check` on every character of a map, given its position, keeping
+/// an error for each one it rejects.
+pub fn each_char<F>(input: &str, mut check: F) -> Vec<ParseError>
+where F: FnMut((usize, usize), char) -> Result<(), String>
+{
+    let mut errors = Vec::new();
+    for (y, line) in error::lines(input).enumerate() {
+        for (x, (start, c)) in line.text.char_indices().enumerate() {
+            if let Err(message) = check((x, y), c) {
+                errors.push(line.error_in(&line.text[start..start + c.len_utf8()], &message));
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that every line of a map is as wide as the first.
+pub fn rectangular(input: &str) -> Vec<ParseError> {
+    let mut width = None;
+    each_line(input, |line| {
+        let len = line.text.chars().count();
+        let expected = *width.get_or_insert(len);
+        let message = format!("expected {} characters, as on line 1, found {}", expected, len);
+        if len < expected {
+            // Points just past the end of the line
+            Err(line.error_in(&line.text[line.text.len()..], &message))
+        }
+        else if len > expected {
+            let extra = line.text.char_indices().nth(expected).unwrap().0;
+            Err(line.error_in(&line.text[extra..], &message))
+        }
+        else {
+            Ok(())
+        }
+    })
+}
+
+/// Checks that a map has `wall` all the way round its edge: every
+/// character of its first and last lines, and the first and last of every
+/// line in between.
+pub fn enclosed(input: &str, wall: char) -> Vec<ParseError> {
+    let widths : Vec<usize> = input.lines().map(|line| line.chars().count()).collect();
+    let message = format!("expected {:?} around the edge of the map", wall);
+    each_char(input, |(x, y), c| {
+        let edge = y == 0 || y + 1 == widths.len() || x == 0 || x + 1 == widths[y];
+        if edge && c != wall { Err(message.clone()) } else { Ok(()) }
+    })
+}
@@ -0,0 +1,141 @@
+//! Tunable constants of a puzzle, such as how many workers day 7 has, so
+//! that the examples, which often use smaller ones, run without editing
+//! code. Each day declares its parameters with the values the real puzzle
+//! uses. They can be overridden from a file next to the input with the
+//! same name and a `.params` extension, holding `name = value` lines, and
+//! then from `--set name=value` on the command line.
+
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One of a day's tunable constants.
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    /// The smallest and largest values the day can run with.
+    pub min: i64,
+    pub max: i64,
+    /// What it's for, for listings.
+    pub about: &'static str,
+}
+
+/// Values given for a day's parameters. Any not given keep their default.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    // In the order they were given, so later ones win
+    values: Vec<(String, i64)>,
+}
+
+pub const EXTENSION : &str = "params";
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.push((name.to_string(), value));
+    }
+
+    /// Sets a parameter from `name=value`.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), String> {
+        let (name, value) = assignment.split_once('=')
+            .ok_or_else(|| format!("expected name=value, found {:?}", assignment))?;
+        let (name, value) = (name.trim(), value.trim());
+        let value = value.parse::<i64>()
+            .map_err(|_| format!("expected a whole number for {}, found {:?}", name, value))?;
+        self.set(name, value);
+        Ok(())
+    }
+
+    /// Whether nothing has been set.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Everything set in `other`, overriding what's set here.
+    pub fn extend(&mut self, other: &Params) {
+        self.values.extend(other.values.iter().cloned());
+    }
+
+    /// The value given for `param`, or its default.
+    pub fn get(&self, param: &Param) -> i64 {
+        self.values.iter().rev().find(|(name, _)| name == param.name).map_or(param.default, |&(_, value)| value)
+    }
+
+    /// The value for `param` as a `T`, or why the day can't run with it.
+    pub fn value<T: TryFrom<i64>>(&self, param: &Param) -> Result<T, String> {
+        let value = self.get(param);
+        if value < param.min || value > param.max {
+            return Err(format!("{} must be from {} to {}, found {}", param.name, param.min, param.max, value));
+        }
+        T::try_from(value).map_err(|_| format!("{} = {} is too big for this machine", param.name, value))
+    }
+
+    /// Checks that every value given is for one of `declared`, and in its
+    /// range.
+    pub fn check(&self, day: u32, declared: &[Param]) -> Result<(), String> {
+        for (name, value) in self.values.iter() {
+            let param = match declared.iter().find(|param| param.name == name) {
+                Some(param) => param,
+                None => {
+                    let known = declared.iter().map(|param| param.name).collect::<Vec<&str>>();
+                    return Err(if known.is_empty() {
+                        format!("day {} has no parameters, but {} was set", day, name)
+                    }
+                    else {
+                        format!("day {} has no parameter {}; it has {}", day, name, known.join(", "))
+                    });
+                }
+            };
+            if *value < param.min || *value > param.max {
+                return Err(format!("day {}'s {} must be from {} to {}, found {}",
+                                   day, name, param.min, param.max, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `name = value` lines from `path`. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub fn load(path: &Path) -> io::Result<Params> {
+        let text = fs::read_to_string(path)?;
+        let mut params = Params::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            params.assign(line).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}: {}", path.display(), index + 1, e))
+            })?;
+        }
+        Ok(params)
+    }
+
+    /// The parameters in the file next to the input file `input_name`, if
+    /// there is one.
+    pub fn for_input(input_name: &str) -> io::Result<Params> {
+        let path = Path::new(input_name).with_extension(EXTENSION);
+        if path.is_file() {
+            Params::load(&path)
+        }
+        else {
+            Ok(Params::default())
+        }
+    }
+}
+
+/// Takes every `--set name=value` out of `args`, adding them to `params`,
+/// and returns what's left.
+pub fn configure<I: IntoIterator<Item = String>>(args: I, params: &mut Params) -> Result<Vec<String>, String> {
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--set" {
+            params.assign(&args.next().ok_or("--set needs name=value")?)?;
+        }
+        else {
+            rest.push(arg);
+        }
+    }
+    Ok(rest)
+}
@@ -0,0 +1,86 @@
+//! Property testing on made-up inputs, for checking a fast implementation
+//! against an obviously correct one. A property is tried on a case made
+//! from each of a run of seeds; the first case it fails on is shrunk to
+//! the smallest one that still fails before it's reported.
+
+use std::fmt::Debug;
+
+use crate::rng::Rng;
+
+/// A case a property failed on, shrunk as far as it would go.
+#[derive(Debug)]
+pub struct Failure<T> {
+    /// The seed the case was first made from.
+    pub seed: u64,
+    pub case: T,
+    /// Why the property failed on the shrunk case.
+    pub message: String,
+    /// How many times the case shrank.
+    pub shrinks: usize,
+}
+
+/// Tries `property` on a case from `generate` for each seed below
+/// `cases`. The first failing case is shrunk: the smaller cases `shrink`
+/// suggests for it are tried in order and the first that still fails takes
+/// its place, until none of them do.
+pub fn find_failure<T, G, S, P>(cases: u64, generate: G, shrink: S, property: P) -> Option<Failure<T>>
+where G: Fn(&mut Rng) -> T, S: Fn(&T) -> Vec<T>, P: Fn(&T) -> Result<(), String>
+{
+    for seed in 0..cases {
+        let case = generate(&mut Rng::new(seed));
+        if let Err(message) = property(&case) {
+            let mut failure = Failure { seed, case, message, shrinks: 0 };
+            'shrinking: loop {
+                for smaller in shrink(&failure.case) {
+                    if let Err(message) = property(&smaller) {
+                        failure.case = smaller;
+                        failure.message = message;
+                        failure.shrinks += 1;
+                        continue 'shrinking;
+                    }
+                }
+                return Some(failure);
+            }
+        }
+    }
+    None
+}
+
+/// As `find_failure`, but panics with the shrunk case if there is one.
+pub fn check<T, G, S, P>(cases: u64, generate: G, shrink: S, property: P)
+where T: Debug, G: Fn(&mut Rng) -> T, S: Fn(&T) -> Vec<T>, P: Fn(&T) -> Result<(), String>
+{
+    if let Some(failure) = find_failure(cases, generate, shrink, property) {
+        panic!("property failed for seed {} (shrunk {} times): {}\n{:#?}",
+               failure.seed, failure.shrinks, failure.message, failure.case);
+    }
+}
+
+/// Passes if `fast` gives what `reference` does.
+pub fn same<T: PartialEq + Debug>(reference: T, fast: T) -> Result<(), String> {
+    if reference == fast {
+        Ok(())
+    }
+    else {
+        Err(format!("reference gave {:?}, fast gave {:?}", reference, fast))
+    }
+}
+
+/// Smaller versions of `items` with at least `min_len` left: each half,
+/// then every way of leaving one out.
+pub fn shrink_vec<T: Clone>(items: &[T], min_len: usize) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    let half = items.len() / 2;
+    if half >= min_len && half > 0 {
+        smaller.push(items[..half].to_vec());
+        smaller.push(items[items.len() - half..].to_vec());
+    }
+    if items.len() > min_len {
+        for i in 0..items.len() {
+            let mut fewer = items.to_vec();
+            fewer.remove(i);
+            smaller.push(fewer);
+        }
+    }
+    smaller
+}
@@ -0,0 +1,124 @@
+//! The `Solution` trait every day implements, and an object-safe view of
+//! it for tools that pick a day at runtime.
+
+use std::any::Any;
+use std::fmt;
+
+use crate::{InputShape, Json, Param, Params, ParseError, Rng};
+
+/// The answer to one part of a puzzle. Besides the answer itself, a day can
+/// report facts it found along the way, such as which guard was asleep,
+/// and explain how they lead to the answer.
+pub trait Answer: fmt::Display {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        Vec::new()
+    }
+
+    /// How the answer was reached, a line at a time, for `--explain`. By
+    /// default, the facts.
+    fn explain(&self) -> Vec<String> {
+        self.facts().into_iter().map(|(name, value)| format!("{}: {}", name, value)).collect()
+    }
+}
+
+impl Answer for i32 {}
+impl Answer for i64 {}
+impl Answer for u32 {}
+impl Answer for u64 {}
+impl Answer for usize {}
+impl Answer for String {}
+
+/// A day's puzzle, split into parsing and the two parts so that the
+/// answers can be computed (and checked) without going through `main()`.
+pub trait Solution {
+    /// The day of the advent calendar this solves.
+    const DAY: u32;
+    /// How the puzzle input is given on the command line.
+    const INPUT: InputShape = InputShape::File;
+    /// The puzzle's tunable constants, with the values the real puzzle
+    /// uses.
+    const PARAMS: &'static [Param] = &[];
+
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    /// As `parse`, with `params` overriding the defaults in `PARAMS`. Days
+    /// with parameters keep the values they need in their input, and
+    /// `parse` passes the defaults.
+    fn parse_with(&self, input: &str, _params: &Params) -> Result<Self::Input, ParseError> {
+        self.parse(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+    fn part2(&self, input: &Self::Input) -> Self::Answer2;
+
+    /// Everything wrong with `input` that would stop it being solved. Days
+    /// whose parsing stops at the first problem, or whose solvers would
+    /// panic on input that parses, check more here; the rest report what
+    /// `parse` finds.
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        self.parse(input).err().into_iter().collect()
+    }
+
+    /// Makes up a puzzle input in the same format as the real one, for
+    /// stress testing. `size` scales it: roughly the number of lines, or
+    /// the side of a map. Both parts should finish on the result.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+/// A `Solution` with its types erased: the parsed input and the answers
+/// are boxed. Solvers keep no state between calls, so one can be shared
+/// by several threads solving different inputs.
+pub trait Solver: Sync {
+    fn day(&self) -> u32;
+    fn input_shape(&self) -> InputShape;
+    fn params(&self) -> &'static [Param];
+    fn parse_input_with(&self, input: &str, params: &Params) -> Result<Box<dyn Any>, ParseError>;
+    fn parse_input(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        self.parse_input_with(input, &Params::default())
+    }
+    /// Everything wrong with `input`, as `Solution::lint` describes.
+    fn lint_input(&self, input: &str) -> Vec<ParseError>;
+    /// Answers `part` (1 or 2) for an input returned by `parse_input`.
+    fn answer(&self, input: &dyn Any, part: u32) -> Box<dyn Answer>;
+    /// A made-up input for `seed`, as `Solution::generate` describes.
+    fn generate_input(&self, seed: u64, size: usize) -> String;
+}
+
+impl<S> Solver for S
+where S: Solution + Sync, S::Input: 'static, S::Answer1: 'static, S::Answer2: 'static
+{
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn input_shape(&self) -> InputShape {
+        S::INPUT
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn parse_input_with(&self, input: &str, params: &Params) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(self.parse_with(input, params)?))
+    }
+
+    fn lint_input(&self, input: &str) -> Vec<ParseError> {
+        self.lint(input)
+    }
+
+    fn answer(&self, input: &dyn Any, part: u32) -> Box<dyn Answer> {
+        let input = input.downcast_ref::<S::Input>().expect("input parsed by a different solver");
+        match part {
+            1 => Box::new(self.part1(input)),
+            2 => Box::new(self.part2(input)),
+            _ => panic!("there is no part {}", part)
+        }
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> String {
+        self.generate(&mut Rng::new(seed), size)
+    }
+}
@@ -0,0 +1,102 @@
+//! Debug output that stays off unless it's asked for on the command line.
+//!
+//! Each trace point names a target: the day's crate, such as `day_06`, or
+//! a part of it, such as `day_15::moves`. `verbose!` points follow the
+//! main steps and show with `-v`; `trace!` points give the detail and show
+//! with `-vv`. `--trace <target>` shows every point in a target and the
+//! targets under it, whatever the level. Output goes to stderr, one line
+//! per point, prefixed with its target.
+
+use std::fmt;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// How much detail a trace point gives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Shown with `-v`.
+    Verbose = 1,
+    /// Shown with `-vv`.
+    Trace = 2,
+}
+
+// These are global, like the standard streams the points write to, so
+// that switching on a point deep inside a day doesn't mean passing
+// anything down to it
+static LEVEL : AtomicU8 = AtomicU8::new(0);
+static TARGETS : RwLock<Vec<String>> = RwLock::new(Vec::new());
+// Saves taking the lock at every point when no target was asked for
+static ANY_TARGETS : AtomicBool = AtomicBool::new(false);
+
+/// Shows every point up to `level`: 1 for `-v`, 2 for `-vv`.
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+/// Shows every point in `target` and the targets under it.
+pub fn enable(target: &str) {
+    TARGETS.write().unwrap().push(target.to_string());
+    ANY_TARGETS.store(true, Ordering::Relaxed);
+}
+
+// Whether switching on `switched_on` covers `target`: it's the same
+// target, or one under it
+fn covers(switched_on: &str, target: &str) -> bool {
+    match target.strip_prefix(switched_on) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false
+    }
+}
+
+/// Whether a point at `level` in `target` should be shown.
+pub fn enabled(level: Level, target: &str) -> bool {
+    if LEVEL.load(Ordering::Relaxed) >= level as u8 {
+        return true;
+    }
+    ANY_TARGETS.load(Ordering::Relaxed)
+        && TARGETS.read().unwrap().iter().any(|switched_on| covers(switched_on, target))
+}
+
+/// Writes out a point that's been found to be enabled. Use `verbose!` or
+/// `trace!` rather than calling this.
+pub fn emit(target: &str, args: fmt::Arguments) {
+    eprintln!("[{}] {}", target, args);
+}
+
+/// Takes `-v`, `-vv` and `--trace <target>` out of `args`, switching
+/// points on to match, and returns what's left.
+pub fn configure<I: IntoIterator<Item = String>>(args: I) -> Result<Vec<String>, String> {
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-v" => set_level(Level::Verbose as u8),
+            "-vv" => set_level(Level::Trace as u8),
+            "--trace" => enable(&args.next().ok_or("--trace needs a target")?),
+            _ => rest.push(arg)
+        }
+    }
+    Ok(rest)
+}
+
+/// A trace point following one of the main steps, shown with `-v`:
+/// `verbose!("day_12", "found a loop at step {}", step)`.
+#[macro_export]
+macro_rules! verbose {
+    ($target:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::Verbose, $target) {
+            $crate::trace::emit($target, format_args!($($arg)+));
+        }
+    };
+}
+
+/// A trace point giving detail, shown with `-vv`:
+/// `trace!("day_15::moves", "looking at {},{}", x, y)`.
+#[macro_export]
+macro_rules! trace {
+    ($target:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace, $target) {
+            $crate::trace::emit($target, format_args!($($arg)+));
+        }
+    };
+}
@@ -0,0 +1,460 @@
+//! Drawing simulations as they run. A day turns its state into a `Frame`
+//! and hands it to a `Backend`, which shows it in a curses window, prints
+//! it to stdout with ANSI escapes, or throws it away when there's no
+//! terminal to draw on. Any of them can also record the frames to a file
+//! to replay later, or save each one as an image.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{Format, Grid, Json};
+
+/// What a cell shows, which decides its color in images.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Kind {
+    Empty,
+    Wall,
+    Track,
+    Elf,
+    Goblin,
+    Cart,
+    Crash,
+    Plant,
+    Particle,
+    Other,
+}
+
+impl Kind {
+    /// The palette for frames that don't choose one: spaces are empty and
+    /// everything else is just something.
+    pub fn plain(c: char) -> Kind {
+        if c == ' ' { Kind::Empty } else { Kind::Other }
+    }
+
+    pub fn color(self) -> [u8; 3] {
+        match self {
+            Kind::Empty => [16, 16, 24],
+            Kind::Wall => [110, 110, 120],
+            Kind::Track => [90, 70, 50],
+            Kind::Elf => [60, 200, 90],
+            Kind::Goblin => [210, 60, 50],
+            Kind::Cart => [240, 200, 40],
+            Kind::Crash => [255, 255, 255],
+            Kind::Plant => [40, 170, 60],
+            Kind::Particle => [250, 250, 210],
+            Kind::Other => [180, 180, 180]
+        }
+    }
+}
+
+/// One picture of a simulation.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    /// The picture itself, one character per cell.
+    pub cells: Grid<char>,
+    /// What each character shows, for coloring images.
+    pub palette: fn(char) -> Kind,
+    /// Notes drawn to the right of each row, such as hit points. May be
+    /// shorter than the picture.
+    pub side: Vec<String>,
+    /// Lines of text under the picture.
+    pub text: Vec<String>,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<char>) -> Frame {
+        Frame { cells, palette: Kind::plain, side: Vec::new(), text: Vec::new() }
+    }
+
+    pub fn with_palette(mut self, palette: fn(char) -> Kind) -> Frame {
+        self.palette = palette;
+        self
+    }
+
+    /// A line of text under the picture.
+    pub fn with_text(mut self, line: &str) -> Frame {
+        self.text.push(line.to_string());
+        self
+    }
+
+    /// Rows of the picture, each followed by its side note if it has one.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines : Vec<String> = (0..self.cells.height()).map(|y| {
+            let row : String = self.cells.row(y).iter().collect();
+            match self.side.get(y) {
+                Some(note) if !note.is_empty() => format!("{} | {}", row, note),
+                _ => row
+            }
+        }).collect();
+        lines.extend(self.text.iter().cloned());
+        lines
+    }
+}
+
+/// Something that can be drawn.
+pub trait Drawable {
+    fn frame(&self) -> Frame;
+}
+
+/// Where frames are shown.
+pub trait Backend {
+    /// Shows `frame` in place of the last one.
+    fn draw(&mut self, frame: &Frame);
+    /// Waits for the viewer to read what's on screen.
+    fn wait(&mut self) {}
+    /// Puts the terminal back the way it was.
+    fn finish(&mut self) {}
+}
+
+/// Draws nothing, for running without a terminal.
+pub struct Headless;
+
+impl Backend for Headless {
+    fn draw(&mut self, _frame: &Frame) {}
+}
+
+/// Prints each frame to stdout, clearing the screen first.
+pub struct Ansi {
+    delay: Duration,
+}
+
+impl Ansi {
+    /// Shows each frame for `delay` before moving on.
+    pub fn new(delay: Duration) -> Ansi {
+        Ansi { delay }
+    }
+}
+
+impl Backend for Ansi {
+    fn draw(&mut self, frame: &Frame) {
+        let mut out = String::from("\x1b[H\x1b[2J");
+        for line in frame.lines() {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        let _ = stdout.write_all(out.as_bytes());
+        let _ = stdout.flush();
+        thread::sleep(self.delay);
+    }
+
+    // Only waits when someone is there to press enter
+    fn wait(&mut self) {
+        if io::stdin().is_terminal() {
+            let mut line = String::new();
+            let _ = io::stdin().read_line(&mut line);
+        }
+    }
+}
+
+/// Draws frames in a curses window, which is opened on the first frame.
+#[cfg(feature = "curses")]
+pub struct Curses {
+    delay: Duration,
+    open: bool,
+}
+
+#[cfg(feature = "curses")]
+impl Curses {
+    /// Shows each frame for `delay` before moving on.
+    pub fn new(delay: Duration) -> Curses {
+        Curses { delay, open: false }
+    }
+
+    fn open(&mut self) {
+        ncurses::initscr();
+        ncurses::noecho();
+        ncurses::curs_set(ncurses::CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+
+        let old_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |arg| {
+            // End the window so that the panic message doesn't get garbled
+            ncurses::endwin();
+            old_hook(arg);
+        }));
+        self.open = true;
+    }
+}
+
+#[cfg(feature = "curses")]
+impl Backend for Curses {
+    fn draw(&mut self, frame: &Frame) {
+        if !self.open {
+            self.open();
+        }
+        ncurses::erase();
+
+        let (width, height) = (frame.cells.width(), frame.cells.height());
+        for ((x, y), c) in frame.cells.iter() {
+            ncurses::mvaddstr(y as i32, x as i32, &c.to_string());
+        }
+        if !frame.side.is_empty() {
+            ncurses::mvvline(0, (width + 1) as i32, '|' as u32, height as i32);
+            for (y, note) in frame.side.iter().enumerate() {
+                ncurses::mvaddstr(y as i32, (width + 3) as i32, note);
+            }
+        }
+        for (i, line) in frame.text.iter().enumerate() {
+            ncurses::mvaddstr((height + 1 + i) as i32, 0, line);
+        }
+
+        ncurses::refresh();
+        thread::sleep(self.delay);
+    }
+
+    fn wait(&mut self) {
+        if self.open {
+            ncurses::getch();
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.open {
+            ncurses::endwin();
+            self.open = false;
+        }
+    }
+}
+
+#[cfg(feature = "curses")]
+impl Drop for Curses {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Writes every frame to an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+/// file as well as showing it, so a run can be replayed with
+/// `asciinema play`.
+pub struct Recorder {
+    backend: Box<dyn Backend>,
+    path: String,
+    // None once writing has failed
+    out: Option<BufWriter<File>>,
+    start: Option<Instant>,
+    // Seconds from the start of the recording to the last frame
+    last: f64,
+    min_gap: f64,
+}
+
+impl Recorder {
+    /// Records to a new file at `path`. Frames are at least `min_gap`
+    /// apart in the recording even when `backend` draws them faster, so
+    /// that a run recorded without a terminal can still be watched.
+    pub fn create(path: &str, backend: Box<dyn Backend>, min_gap: Duration) -> io::Result<Recorder> {
+        Ok(Recorder {
+            backend,
+            path: path.to_string(),
+            out: Some(BufWriter::new(File::create(path)?)),
+            start: None,
+            last: 0.0,
+            min_gap: min_gap.as_secs_f64()
+        })
+    }
+
+    fn record(&mut self, frame: &Frame) -> io::Result<()> {
+        let lines = frame.lines();
+        let out = match self.out {
+            Some(ref mut out) => out,
+            None => return Ok(())
+        };
+        let time = match self.start {
+            None => {
+                self.start = Some(Instant::now());
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+                let header = Json::object(vec![
+                    ("version", 2.into()),
+                    ("width", lines.iter().map(|line| line.chars().count()).max().unwrap_or(0).into()),
+                    ("height", lines.len().into()),
+                    ("timestamp", timestamp.into()),
+                ]);
+                writeln!(out, "{}", header)?;
+                0.0
+            },
+            Some(start) => start.elapsed().as_secs_f64().max(self.last + self.min_gap)
+        };
+        self.last = time;
+
+        // Clear the screen and draw the frame from the top left
+        let screen = format!("\x1b[H\x1b[2J{}", lines.join("\r\n"));
+        let event = Json::Array(vec![((time * 1e6).round() / 1e6).into(), "o".into(), screen.into()]);
+        writeln!(out, "{}", event)
+    }
+}
+
+impl Backend for Recorder {
+    fn draw(&mut self, frame: &Frame) {
+        self.backend.draw(frame);
+        if let Err(e) = self.record(frame) {
+            eprintln!("warning: stopped recording to {}: {}", self.path, e);
+            self.out = None;
+        }
+    }
+
+    fn wait(&mut self) {
+        self.backend.wait();
+    }
+
+    fn finish(&mut self) {
+        self.backend.finish();
+        if let Some(ref mut out) = self.out {
+            if let Err(e) = out.flush() {
+                eprintln!("warning: couldn't finish recording to {}: {}", self.path, e);
+            }
+        }
+    }
+}
+
+/// Side of the square of pixels each cell becomes in an image.
+const PIXELS_PER_CELL : usize = 4;
+
+/// The kinds of image a frame can be saved as.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png"
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ImageFormat, String> {
+        match s {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "png" => Ok(ImageFormat::Png),
+            _ => Err(format!("unknown image format {} (expected ppm or png)", s))
+        }
+    }
+}
+
+/// Saves the picture in `frame` as an image, coloring each cell by its
+/// kind. The text around the picture is left out.
+pub fn write_image(frame: &Frame, path: &Path, format: ImageFormat) -> io::Result<()> {
+    let (width, height) = (frame.cells.width() * PIXELS_PER_CELL, frame.cells.height() * PIXELS_PER_CELL);
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            let c = frame.cells[(x / PIXELS_PER_CELL, y / PIXELS_PER_CELL)];
+            pixels.extend_from_slice(&(frame.palette)(c).color());
+        }
+    }
+
+    let mut out = BufWriter::new(File::create(path)?);
+    match format {
+        ImageFormat::Ppm => {
+            write!(out, "P6\n{} {}\n255\n", width, height)?;
+            out.write_all(&pixels)?;
+        },
+        ImageFormat::Png => {
+            let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().map_err(io::Error::other)?;
+            writer.write_image_data(&pixels).map_err(io::Error::other)?;
+        }
+    }
+    out.flush()
+}
+
+/// Saves every frame as a numbered image in a directory, as well as
+/// showing it.
+pub struct FrameWriter {
+    backend: Box<dyn Backend>,
+    dir: PathBuf,
+    format: ImageFormat,
+    count: usize,
+    failed: bool,
+}
+
+impl FrameWriter {
+    /// Writes to `dir`, which is created if it doesn't exist.
+    pub fn create(dir: &str, format: ImageFormat, backend: Box<dyn Backend>) -> io::Result<FrameWriter> {
+        fs::create_dir_all(dir)?;
+        Ok(FrameWriter { backend, dir: PathBuf::from(dir), format, count: 0, failed: false })
+    }
+}
+
+impl Backend for FrameWriter {
+    fn draw(&mut self, frame: &Frame) {
+        self.backend.draw(frame);
+        if self.failed {
+            return;
+        }
+        let path = self.dir.join(format!("frame_{:05}.{}", self.count, self.format.extension()));
+        if let Err(e) = write_image(frame, &path, self.format) {
+            eprintln!("warning: stopped writing frames: {}: {}", path.display(), e);
+            self.failed = true;
+        }
+        self.count += 1;
+    }
+
+    fn wait(&mut self) {
+        self.backend.wait();
+    }
+
+    fn finish(&mut self) {
+        self.backend.finish();
+    }
+}
+
+/// Which backend to draw with, as chosen by `--viz`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Viz {
+    Curses,
+    Ansi,
+    Headless,
+}
+
+impl FromStr for Viz {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Viz, String> {
+        match s {
+            "curses" if cfg!(feature = "curses") => Ok(Viz::Curses),
+            "curses" => Err("this build has no curses support".to_string()),
+            "ansi" => Ok(Viz::Ansi),
+            "none" => Ok(Viz::Headless),
+            _ => Err(format!("unknown visualization {} (expected curses, ansi or none)", s))
+        }
+    }
+}
+
+impl Viz {
+    /// Draws in the terminal when there is one and the answers are meant
+    /// for people; otherwise draws nothing.
+    pub fn default_for(format: Format) -> Viz {
+        if format == Format::Json || !io::stdout().is_terminal() {
+            Viz::Headless
+        }
+        else if cfg!(feature = "curses") {
+            Viz::Curses
+        }
+        else {
+            Viz::Ansi
+        }
+    }
+
+    /// A backend that shows each frame for `delay`.
+    pub fn backend(self, delay: Duration) -> Box<dyn Backend> {
+        match self {
+            #[cfg(feature = "curses")]
+            Viz::Curses => Box::new(Curses::new(delay)),
+            Viz::Ansi => Box::new(Ansi::new(delay)),
+            _ => Box::new(Headless)
+        }
+    }
+}
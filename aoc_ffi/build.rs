@@ -0,0 +1,18 @@
+extern crate cbindgen;
+
+use std::env;
+use std::path::Path;
+
+// Writes include/aoc.h from the functions in src/lib.rs, leaving it alone
+// if nothing in it would change
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let crate_dir = Path::new(&crate_dir);
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    cbindgen::generate_with_config(crate_dir, config)
+        .expect("couldn't generate include/aoc.h")
+        .write_to_file(crate_dir.join("include").join("aoc.h"));
+}
@@ -0,0 +1,63 @@
+/* The solvers' C ABI. Written by aoc_ffi's build script from src/lib.rs; don't edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// What a call came to. Anything but `AOC_STATUS_OK` leaves a message in
+// the output buffer instead of an answer, if it fits.
+typedef enum AocStatus {
+  // The answer is in the output buffer.
+  AOC_STATUS_OK = 0,
+  // There's no solver for the day asked for.
+  AOC_STATUS_NO_SUCH_DAY = 1,
+  // The day has no such part.
+  AOC_STATUS_NO_SUCH_PART = 2,
+  // The input or the output buffer was null.
+  AOC_STATUS_NULL_POINTER = 3,
+  // The input isn't UTF-8.
+  AOC_STATUS_INVALID_UTF8 = 4,
+  // The input couldn't be parsed. The message gives the line and
+  // column, as `<line>:<col>: <message>`.
+  AOC_STATUS_PARSE_ERROR = 5,
+  // The solver panicked on the input.
+  AOC_STATUS_PANICKED = 6,
+  // The output buffer can't hold the answer and its terminating NUL.
+  AOC_STATUS_BUFFER_TOO_SMALL = 7,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Solves `part` of `day` for the `input_len` bytes of input at
+// `input_ptr`: the puzzle input, or the values for days that take values,
+// separated by whitespace. The answer is written to `out_buf`, which
+// holds `out_len` bytes, as a NUL-terminated string. Multi-line answers
+// keep their newlines.
+//
+// # Safety
+//
+// `input_ptr` must point to `input_len` readable bytes and `out_buf` to
+// `out_len` writable ones.
+enum AocStatus aoc_solve(uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input_ptr,
+                         size_t input_len,
+                         char *out_buf,
+                         size_t out_len);
+
+// A static, NUL-terminated description of a status returned by
+// `aoc_solve`.
+const char *aoc_status_name(int32_t status);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC_H */
@@ -0,0 +1,130 @@
+//! The solvers behind a C ABI, for test harnesses written in C or C++.
+//! `include/aoc.h` declares these functions; it's written by the build
+//! script from this file, so it changes along with it.
+//!
+//! Nothing here keeps state between calls, so the functions may be called
+//! from several threads at once.
+
+extern crate aoc;
+extern crate aoc_common;
+
+use std::convert::TryFrom;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use std::str;
+
+use aoc_common::InputShape;
+
+/// What a call came to. Anything but `AOC_STATUS_OK` leaves a message in
+/// the output buffer instead of an answer, if it fits.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The answer is in the output buffer.
+    Ok = 0,
+    /// There's no solver for the day asked for.
+    NoSuchDay = 1,
+    /// The day has no such part.
+    NoSuchPart = 2,
+    /// The input or the output buffer was null.
+    NullPointer = 3,
+    /// The input isn't UTF-8.
+    InvalidUtf8 = 4,
+    /// The input couldn't be parsed. The message gives the line and
+    /// column, as `<line>:<col>: <message>`.
+    ParseError = 5,
+    /// The solver panicked on the input.
+    Panicked = 6,
+    /// The output buffer can't hold the answer and its terminating NUL.
+    BufferTooSmall = 7,
+}
+
+// Copies `text` into the caller's buffer, NUL-terminated, or as much of it
+// as fits
+fn write_out(text: &str, out_buf: *mut c_char, out_len: usize) -> bool {
+    if out_len == 0 {
+        return false;
+    }
+    let len = std::cmp::min(text.len(), out_len - 1);
+    // SAFETY: the caller promises `out_buf` holds `out_len` bytes
+    unsafe {
+        std::ptr::copy_nonoverlapping(text.as_ptr(), out_buf as *mut u8, len);
+        *out_buf.add(len) = 0;
+    }
+    len == text.len()
+}
+
+// The answer to one part, or why there isn't one
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<String, (AocStatus, String)> {
+    let day = aoc::find(day).ok_or_else(|| (AocStatus::NoSuchDay, format!("day {} is not implemented", day)))?;
+    if !aoc::PARTS.contains(&part) {
+        return Err((AocStatus::NoSuchPart, format!("day {} has no part {}", day.number, part)));
+    }
+    let text = str::from_utf8(input).map_err(|e| (AocStatus::InvalidUtf8, e.to_string()))?;
+    // Values come joined by single spaces, as they would from the command line
+    let text = match day.solver.input_shape() {
+        InputShape::File => text.to_string(),
+        InputShape::Values(_) => text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    };
+
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        day.solver.parse_input(&text).map(|parsed| day.solver.answer(&*parsed, part).to_string())
+    }));
+    match solved {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err((AocStatus::ParseError, format!("{}:{}: {}", e.line, e.col, e.message))),
+        Err(_) => Err((AocStatus::Panicked, format!("the solver for day {} panicked", day.number)))
+    }
+}
+
+/// Solves `part` of `day` for the `input_len` bytes of input at
+/// `input_ptr`: the puzzle input, or the values for days that take values,
+/// separated by whitespace. The answer is written to `out_buf`, which
+/// holds `out_len` bytes, as a NUL-terminated string. Multi-line answers
+/// keep their newlines.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes and `out_buf` to
+/// `out_len` writable ones.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input_ptr: *const u8, input_len: usize,
+                                   out_buf: *mut c_char, out_len: usize) -> AocStatus {
+    if out_buf.is_null() {
+        return AocStatus::NullPointer;
+    }
+    if input_ptr.is_null() {
+        write_out("the input is null", out_buf, out_len);
+        return AocStatus::NullPointer;
+    }
+    let input = slice::from_raw_parts(input_ptr, input_len);
+    match solve(day, part, input) {
+        Ok(answer) => if write_out(&answer, out_buf, out_len) { AocStatus::Ok } else { AocStatus::BufferTooSmall },
+        Err((status, message)) => {
+            write_out(&message, out_buf, out_len);
+            status
+        }
+    }
+}
+
+// Indexed by status
+const STATUS_NAMES : [&[u8]; 8] = [
+    b"ok\0",
+    b"no such day\0",
+    b"no such part\0",
+    b"null pointer\0",
+    b"input is not UTF-8\0",
+    b"parse error\0",
+    b"solver panicked\0",
+    b"output buffer too small\0",
+];
+
+/// A static, NUL-terminated description of a status returned by
+/// `aoc_solve`.
+#[no_mangle]
+pub extern "C" fn aoc_status_name(status: i32) -> *const c_char {
+    let name = usize::try_from(status).ok().and_then(|index| STATUS_NAMES.get(index)).copied()
+        .unwrap_or(b"unknown status\0");
+    name.as_ptr() as *const c_char
+}
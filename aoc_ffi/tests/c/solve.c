@@ -0,0 +1,57 @@
+/* Calls the solvers through aoc.h the way a C harness would. Exits with
+ * the number of checks that failed. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+/* Solves a part and checks both the status and what was written out */
+static void check(uint32_t day, uint32_t part, const char *input, size_t out_len,
+                  AocStatus expected_status, const char *expected) {
+    char out[256];
+    AocStatus status = aoc_solve(day, part, (const uint8_t *)input, strlen(input), out, out_len);
+    if (status != expected_status || strcmp(out, expected) != 0) {
+        fprintf(stderr, "day %u part %u: expected %s \"%s\", got %s \"%s\"\n", day, part,
+                aoc_status_name(expected_status), expected, aoc_status_name(status), out);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *changes = "+3\n+3\n+4\n-2\n-4\n";
+    const char *cave =
+        "#######\n"
+        "#.G...#\n"
+        "#...EG#\n"
+        "#.#.#G#\n"
+        "#..G#E#\n"
+        "#.....#\n"
+        "#######\n";
+
+    check(1, 1, changes, 256, AOC_STATUS_OK, "4");
+    check(1, 2, changes, 256, AOC_STATUS_OK, "10");
+    check(15, 1, cave, 256, AOC_STATUS_OK, "27730");
+    check(15, 2, cave, 256, AOC_STATUS_OK, "4988");
+
+    check(1, 1, "+3\n+x\n", 256, AOC_STATUS_PARSE_ERROR, "2:1: expected a frequency change such as +1");
+    check(15, 1, "#.X#\n", 256, AOC_STATUS_PARSE_ERROR, "1:3: expected one of #.GE, found 'X'");
+    check(0, 1, changes, 256, AOC_STATUS_NO_SUCH_DAY, "day 0 is not implemented");
+    check(1, 3, changes, 256, AOC_STATUS_NO_SUCH_PART, "day 1 has no part 3");
+    /* Inputs the solvers would never finish are refused, or given up on */
+    check(7, 1, "Step A must be finished before step B can begin.\n"
+                "Step B must be finished before step A can begin.\n", 256,
+          AOC_STATUS_PARSE_ERROR, "1:1: A and B wait on each other through a cycle of requirements");
+    check(15, 1, "#######\n#E.#.G#\n#######\n", 256,
+          AOC_STATUS_PARSE_ERROR, "2:2: no elf can ever reach a goblin, so the fight could never end");
+    check(13, 1, "/->-\\\n|   |\n\\-<-/\n", 256, AOC_STATUS_PANICKED, "the solver for day 13 panicked");
+    /* Room for the first four digits of the answer and a NUL */
+    check(15, 1, cave, 5, AOC_STATUS_BUFFER_TOO_SMALL, "2773");
+
+    if (failures == 0) {
+        printf("all checks passed\n");
+    }
+    return failures;
+}
@@ -0,0 +1,41 @@
+//! Builds the C program in tests/c against the library and aoc.h, and runs
+//! it. It's skipped where there's no C compiler.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Where cargo put the library as it built it for this test: next to the
+// test's executable. The copy a directory up is only refreshed by cargo
+// build.
+fn library_dir() -> PathBuf {
+    env::current_exe().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn c_program_solves_through_the_header() {
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    if Command::new(&cc).arg("--version").output().is_err() {
+        eprintln!("skipping: no C compiler ({})", cc);
+        return;
+    }
+
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = library_dir();
+    let program = lib_dir.join("aoc_ffi_solve");
+    let built = Command::new(&cc)
+        .arg(crate_dir.join("tests").join("c").join("solve.c"))
+        .arg("-I").arg(crate_dir.join("include"))
+        // By path, so that the program loads this copy rather than one
+        // found on the library path cargo sets for tests
+        .arg(lib_dir.join(format!("{}aoc_ffi{}", env::consts::DLL_PREFIX, env::consts::DLL_SUFFIX)))
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-Wall").arg("-Werror")
+        .arg("-o").arg(&program)
+        .status().unwrap();
+    assert!(built.success(), "couldn't build the C program");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "all checks passed\n");
+}
@@ -0,0 +1,69 @@
+extern crate aoc_common;
+
+use std::collections::HashSet;
+
+use aoc_common::{ParseError, Rng, Solution};
+
+#[derive(Default)]
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY : u32 = 1;
+
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        let changes = aoc_common::lines(input).map(|line| {
+            line.parse::<i32>(line.text, "a frequency change such as +1")
+        }).collect::<Result<Vec<i32>, ParseError>>()?;
+        if changes.is_empty() {
+            return Err(ParseError::at_end(input, "expected a frequency change such as +1"));
+        }
+        Ok(changes)
+    }
+
+    fn part1(&self, lines: &Vec<i32>) -> i32 {
+        lines.iter().sum()
+    }
+
+    fn part2(&self, lines: &Vec<i32>) -> i32 {
+        // Each pass reaches the frequencies of the first one moved on by
+        // the same drift, so once the drift has carried them past the
+        // spread of the first pass, none can come round again
+        let first_pass : Vec<i64> = lines.iter().scan(0, |freq, &line| {
+            *freq += line as i64;
+            Some(*freq)
+        }).collect();
+        let drift = first_pass.last().map_or(0, |freq| freq.abs());
+        let spread = first_pass.iter().max().unwrap_or(&0) - first_pass.iter().min().unwrap_or(&0);
+        let passes = if drift == 0 { 2 } else { spread / drift + 2 };
+
+        let mut freq : i32 = 0;
+        let mut seen : HashSet<i32> = HashSet::new();
+        for _ in 0..passes {
+            for line in lines {
+                freq += line;
+                if seen.contains(&freq) {
+                    return freq;
+                }
+                seen.insert(freq);
+            }
+        }
+        panic!("no frequency is ever reached twice");
+    }
+
+    // The changes add up to a small drift, so that part 2 always finds a
+    // frequency it has seen before
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let count = std::cmp::max(size, 8);
+        let mut changes : Vec<i64> = (0..count - 1).map(|_| {
+            let change = rng.between(1, 20);
+            if rng.chance(0.5) { change } else { -change }
+        }).collect();
+        let drift = rng.between(-3, 3);
+        changes.push(drift - changes.iter().sum::<i64>());
+        changes.iter().map(|change| format!("{:+}\n", change)).collect()
+    }
+}
@@ -0,0 +1,127 @@
+extern crate aoc_common;
+
+use std::collections::HashMap;
+
+use aoc_common::{ParseError, Rng, Solution};
+
+fn n_duplicates(n :i32, string: &str) -> i32 {
+    let mut table = [0; 256];
+    let mut result : i32 = 0;
+    for byte in string.as_bytes().iter() {
+        table[*byte as usize] += 1;
+    }
+    for b in table.iter() {
+        if *b == n {
+            result += 1;
+        }
+    }
+    result
+}
+
+// The ID with the letter at `k` taken out
+fn without(id: &str, k: usize) -> String {
+    id.bytes().enumerate().filter(|&(i, _)| i != k).map(|(_, b)| b as char).collect()
+}
+
+/// The letters in common between the first ID in the list that has a twin
+/// (another ID differing from it in exactly one place) and its first twin.
+/// IDs are all the same length. Each is filed under every way of taking
+/// one letter out, so twins end up filed together.
+pub fn find_twins(ids: &[String]) -> Option<String> {
+    let mut filed : HashMap<(usize, String), Vec<usize>> = HashMap::new();
+    for (idx, id) in ids.iter().enumerate() {
+        for k in 0..id.len() {
+            filed.entry((k, without(id, k))).or_default().push(idx);
+        }
+    }
+
+    for id in ids {
+        let twin = (0..id.len()).filter_map(|k| {
+            filed[&(k, without(id, k))].iter().find(|&&other| ids[other] != *id).map(|&other| (other, k))
+        }).min();
+        if let Some((_, k)) = twin {
+            return Some(without(id, k));
+        }
+    }
+    None
+}
+
+/// The obviously correct way, kept to check `find_twins` against.
+pub mod reference {
+    fn diff_strs (a: &str, b: &str) -> String {
+        let a_bytes = a.as_bytes();
+        let b_bytes = b.as_bytes();
+
+        let mut common_chars = Vec::<char>::new();
+        for (a, b) in a_bytes.iter().zip(b_bytes.iter()) {
+            if a == b {
+                common_chars.push(*a as char);
+            }
+        }
+        common_chars.into_iter().collect()
+    }
+
+    /// As `super::find_twins`, comparing every pair of IDs.
+    pub fn find_twins(ids: &[String]) -> Option<String> {
+        for line in ids {
+            for otherline in ids {
+                let remove_diffs = diff_strs(line, otherline);
+                if remove_diffs.len() == line.len() - 1 {
+                    return Some(remove_diffs);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Default)]
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY : u32 = 2;
+
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(|x| x.to_string()).collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> i32 {
+        let mut check_2 = 0;
+        let mut check_3 = 0;
+        for line in lines {
+            if n_duplicates(2, line) > 0 {
+                check_2 += 1;
+            }
+            if n_duplicates(3, line) > 0 {
+                check_3 += 1;
+            }
+        }
+        check_2 * check_3
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> String {
+        find_twins(lines).expect("No two box IDs differ by exactly one character")
+    }
+
+    // Random IDs, and one more that differs from one of them by a single
+    // letter
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let letters : Vec<char> = ('a'..='z').collect();
+        let mut ids : Vec<String> = (0..std::cmp::max(size, 2) - 1).map(|_| {
+            (0..letters.len()).map(|_| *rng.pick(&letters)).collect()
+        }).collect();
+
+        let mut twin : Vec<char> = rng.pick(&ids).chars().collect();
+        let i = rng.below(twin.len());
+        let letter = (twin[i] as u8 - b'a') as i64 + rng.between(1, 25);
+        twin[i] = (b'a' + (letter % 26) as u8) as char;
+        ids.push(twin.into_iter().collect());
+
+        rng.shuffle(&mut ids);
+        ids.iter().map(|id| format!("{}\n", id)).collect()
+    }
+}
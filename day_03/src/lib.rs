@@ -0,0 +1,172 @@
+extern crate aoc_common;
+extern crate regex;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use aoc_common::lint;
+use aoc_common::{trace, Answer, Grid, Json, Line, ParseError, Rng, Solution};
+use regex::Regex;
+
+#[derive(Clone)]
+pub struct Claim {
+    id: i32,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32
+}
+
+impl Claim {
+    fn squares(&self) -> impl Iterator<Item = (usize, usize)> {
+        let &Claim { x: x_0, y: y_0, w, h, .. } = self;
+        (y_0..(y_0 + h)).flat_map(move |y| (x_0..(x_0 + w)).map(move |x| (x as usize, y as usize)))
+    }
+}
+
+/// The squares of fabric inside two or more claims, and how many of the
+/// claims take part in an overlap.
+pub struct Overlap {
+    pub squares: usize,
+    pub claims: usize,
+    pub overlapping_claims: usize,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.squares)
+    }
+}
+
+impl Answer for Overlap {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        vec![("squares", self.squares.into()),
+             ("claims", self.claims.into()),
+             ("overlapping_claims", self.overlapping_claims.into())]
+    }
+
+    fn explain(&self) -> Vec<String> {
+        vec![format!("{} of the {} claims overlap another", self.overlapping_claims, self.claims),
+             format!("{} square inches are inside two or more claims", self.squares)]
+    }
+}
+
+/// The one claim that overlaps no other. The answer is its id.
+pub struct Intact(Claim);
+
+impl fmt::Display for Intact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.id)
+    }
+}
+
+impl Answer for Intact {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        let Intact(Claim { id, x, y, w, h }) = self;
+        vec![("claim", (*id).into()), ("x", (*x).into()), ("y", (*y).into()), ("width", (*w).into()), ("height", (*h).into())]
+    }
+
+    fn explain(&self) -> Vec<String> {
+        let Intact(Claim { id, x, y, w, h }) = self;
+        vec![format!("claim #{} @ {},{}: {}x{} overlaps no other claim", id, x, y, w, h)]
+    }
+}
+
+// Lays out every claim and counts how many claims cover each square
+fn fabric(claims: &[Claim]) -> Grid<u32> {
+    let width = claims.iter().map(|claim| claim.x + claim.w).max().unwrap_or(0);
+    let height = claims.iter().map(|claim| claim.y + claim.h).max().unwrap_or(0);
+    let mut fabric = Grid::new(width as usize, height as usize, 0);
+
+    for claim in claims {
+        for square in claim.squares() {
+            trace!("day_03::fabric", "claim #{} covers ({},{})", claim.id, square.0, square.1);
+            fabric[square] += 1;
+        }
+    }
+    fabric
+}
+
+fn claim_regex() -> Regex {
+    Regex::new(r"^#(\d+)\s@\s(\d+),(\d+):\s(\d+)x(\d+)$").unwrap()
+}
+
+// The widest and tallest the fabric can be. The puzzle's is at least a
+// thousand inches each way; this keeps a grid of it well within memory.
+const MAX_FABRIC_SIDE : i64 = 4_000;
+
+fn parse_claim(re: &Regex, line: Line) -> Result<Claim, ParseError> {
+    trace!("day_03::parse", "reading line: {}", &line.text);
+    let captures = re.captures(line.text).ok_or_else(|| line.error("expected a claim such as #1 @ 1,3: 4x4"))?;
+    let coords = captures.iter().skip(1).map(|x| {
+        line.parse::<i32>(x.unwrap().as_str(), "a number that fits in an i32")
+    }).collect::<Result<Vec<i32>, ParseError>>()?;
+    for (start, size) in [(1, 3), (2, 4)] {
+        if coords[start] as i64 + coords[size] as i64 > MAX_FABRIC_SIDE {
+            return Err(line.error_in(captures.get(size + 1).unwrap().as_str(),
+                                     &format!("expected the claim to fit in a fabric {} inches square", MAX_FABRIC_SIDE)));
+        }
+    }
+    trace!("day_03::parse", "parsed as #{} @ {},{} {}x{}", coords[0], coords[1], coords[2], coords[3], coords[4]);
+    Ok(Claim { id: coords[0], x: coords[1], y: coords[2], w: coords[3], h: coords[4] })
+}
+
+#[derive(Default)]
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY : u32 = 3;
+
+    type Input = Vec<Claim>;
+    type Answer1 = Overlap;
+    type Answer2 = Intact;
+
+    fn parse(&self, input: &str) -> Result<Vec<Claim>, ParseError> {
+        let re = claim_regex();
+        aoc_common::lines(input).map(|line| parse_claim(&re, line)).collect()
+    }
+
+    // Every line is a claim, and no two claims have the same id
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let re = claim_regex();
+        let mut seen = HashMap::new();
+        lint::each_line(input, |line| {
+            let claim = parse_claim(&re, line)?;
+            match seen.insert(claim.id, line.number) {
+                Some(first) => Err(line.error(&format!("claim #{} was already made on line {}", claim.id, first))),
+                None => Ok(())
+            }
+        })
+    }
+
+    fn part1(&self, claims: &Vec<Claim>) -> Overlap {
+        let fabric = fabric(claims);
+        let squares = fabric.iter().filter(|(_, &count)| count > 1).count();
+        let overlapping_claims = claims.iter().filter(|claim| claim.squares().any(|square| fabric[square] > 1)).count();
+        Overlap { squares, claims: claims.len(), overlapping_claims }
+    }
+
+    fn part2(&self, claims: &Vec<Claim>) -> Intact {
+        let fabric = fabric(claims);
+        claims.iter().filter(|claim| {
+            claim.squares().all(|square| fabric[square] == 1)
+        }).min_by_key(|claim| claim.id).map(|claim| Intact(claim.clone())).expect("Every claim overlaps another")
+    }
+
+    // Claims heaped on top of each other, and one more off to the side that
+    // overlaps none of them
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let count = std::cmp::max(size, 1);
+        let side = std::cmp::min(10 + 3 * count as i64, MAX_FABRIC_SIDE - 70);
+        let mut claims : Vec<(i64, i64, i64, i64)> = (0..count).map(|_| {
+            (rng.between(0, side), rng.between(0, side), rng.between(1, 30), rng.between(1, 30))
+        }).collect();
+        let right = claims.iter().map(|&(x, _, w, _)| x + w).max().unwrap();
+        claims.push((right + rng.between(0, 5), rng.between(0, side), rng.between(1, 30), rng.between(1, 30)));
+
+        rng.shuffle(&mut claims);
+        claims.iter().zip(1..).map(|(&(x, y, w, h), id)| {
+            format!("#{} @ {},{}: {}x{}\n", id, x, y, w, h)
+        }).collect()
+    }
+}
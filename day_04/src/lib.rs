@@ -0,0 +1,275 @@
+extern crate aoc_common;
+extern crate regex;
+extern crate chrono;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use aoc_common::lint;
+use aoc_common::{trace, verbose, Answer, Json, Line, ParseError, Rng, Solution};
+use regex::Regex;
+
+use chrono::{NaiveDate, NaiveDateTime, Duration, Timelike};
+
+pub struct Guard {
+    sleeping_from : Option<NaiveDateTime>,
+    total_slept_min : i32,
+    // Nights asleep on each minute of the midnight hour
+    slept_by_minute : [i32; 60]
+}
+
+impl Guard {
+    fn new() -> Guard {
+        Guard {
+            sleeping_from: None,
+            total_slept_min: 0,
+            slept_by_minute: [0; 60]
+        }
+    }
+
+    // The minute this guard was most often asleep, and how often. The
+    // earliest minute wins a tie.
+    fn sleepiest_minute(&self) -> (u32, i32) {
+        self.slept_by_minute.iter().zip(0..).fold((0, 0), |(max_minute, max_count), (&count, minute)| {
+            if max_count < count {
+                (minute, count)
+            }
+            else {
+                (max_minute, max_count)
+            }
+        })
+    }
+}
+
+/// How a guard is picked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// Part 1: the guard asleep for the most minutes.
+    MostAsleep,
+    /// Part 2: the guard most often asleep on the same minute.
+    MostOften,
+}
+
+/// A guard picked by one of the strategies, and the minute they're most
+/// often asleep. The answer is the id times the minute.
+pub struct Sleepiest {
+    pub strategy: Strategy,
+    pub id: i32,
+    pub minute: u32,
+    /// Minutes asleep in all.
+    pub total_asleep: i32,
+    /// Nights asleep on `minute`.
+    pub times_on_minute: i32,
+}
+
+impl fmt::Display for Sleepiest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id as u64 * self.minute as u64)
+    }
+}
+
+impl Answer for Sleepiest {
+    // `asleep` is the count the strategy went by
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        let asleep = match self.strategy {
+            Strategy::MostAsleep => self.total_asleep,
+            Strategy::MostOften => self.times_on_minute
+        };
+        vec![("guard", self.id.into()),
+             ("minute", self.minute.into()),
+             ("asleep", asleep.into()),
+             ("total_asleep", self.total_asleep.into()),
+             ("times_on_minute", self.times_on_minute.into())]
+    }
+
+    fn explain(&self) -> Vec<String> {
+        let picked = match self.strategy {
+            Strategy::MostAsleep => format!("guard #{} slept the most, {} minutes in all, and was asleep at 00:{:02} most often, on {} nights",
+                                            self.id, self.total_asleep, self.minute, self.times_on_minute),
+            Strategy::MostOften => format!("guard #{} was asleep at 00:{:02} on {} nights, more often than any guard on any minute",
+                                           self.id, self.minute, self.times_on_minute)
+        };
+        vec![picked, format!("{} × {} = {}", self.id, self.minute, self)]
+    }
+}
+
+fn record_regex() -> Regex {
+    Regex::new(r"^\[([^\]]+)\] (.*)$").unwrap()
+}
+
+// The time of a record, as written and as read, and what happened
+fn parse_record<'a>(re: &Regex, line: Line<'a>) -> Result<(&'a str, NaiveDateTime, &'a str), ParseError> {
+    let captures = re.captures(line.text).ok_or_else(|| {
+        line.error("expected a record such as [1518-11-01 00:00] falls asleep")
+    })?;
+    let date_str = captures.get(1).unwrap().as_str();
+    let timestamp = NaiveDateTime::parse_from_str(date_str, "%F %H:%M").map_err(|_| {
+        line.error_in(date_str, "expected a timestamp such as 1518-11-01 00:00")
+    })?;
+    Ok((date_str, timestamp, captures.get(2).unwrap().as_str()))
+}
+
+#[derive(Default)]
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY : u32 = 4;
+
+    type Input = HashMap<i32,Guard>;
+    type Answer1 = Sleepiest;
+    type Answer2 = Sleepiest;
+
+    fn parse(&self, input: &str) -> Result<HashMap<i32,Guard>, ParseError> {
+        let re = record_regex();
+        let begin_re : Regex = Regex::new(r"Guard #(\d+) begins shift").unwrap();
+        let sleep_re : Regex = Regex::new(r"falls asleep").unwrap();
+        let wakes_re : Regex = Regex::new(r"wakes up").unwrap();
+
+        let mut records = Vec::new();
+        for line in aoc_common::lines(input) {
+            let (stamp, timestamp, event) = parse_record(&re, line)?;
+            records.push((timestamp, stamp, event, line));
+        }
+        records.sort_by_key(|(timestamp, _, _, _)| *timestamp);
+
+        let mut guards = HashMap::<i32,Guard>::new();
+        let mut cur_guard : Option<i32> = None;
+
+        for (timestamp, stamp, event, line) in records {
+            let timestamp = &timestamp;
+            trace!("day_04::records", "[{}] {}", timestamp, event);
+
+            if let Some(capture) = begin_re.captures(event) {
+                let guard_num = line.parse::<i32>(capture.get(1).unwrap().as_str(), "a guard number")?;
+                cur_guard = Some(guard_num);
+                // add a new guard if we haven't seen this one before
+                guards.entry(guard_num).or_insert(Guard::new());
+                trace!("day_04::records", "switched to guard {}", guard_num);
+            }
+            else if let Some(ref guard_num) = cur_guard {
+                if let Some(g) = guards.get_mut(guard_num) {
+                    if sleep_re.is_match(event) {
+                        if timestamp.hour() != 0 {
+                            return Err(line.error_in(stamp, "expected the guard to fall asleep between 00:00 and 00:59"));
+                        }
+                        g.sleeping_from = Some(*timestamp);
+                        trace!("day_04::records", "guard {} sleeping at {}", guard_num, timestamp);
+                    }
+                    else if wakes_re.is_match(event) {
+                        let sleeping_from = g.sleeping_from.ok_or_else(|| {
+                            line.error_in(event, "the guard wakes up without falling asleep")
+                        })?;
+                        // Naps are within the midnight hour of one night
+                        if timestamp.date() != sleeping_from.date() || timestamp.hour() != 0 || *timestamp == sleeping_from {
+                            return Err(line.error_in(stamp, &format!("expected the guard to wake up after falling asleep at {} and by 00:59",
+                                                                     sleeping_from.format("%F %H:%M"))));
+                        }
+                        let (asleep, awake) = (sleeping_from.minute() as usize, timestamp.minute() as usize);
+                        g.total_slept_min += (awake - asleep) as i32;
+                        g.sleeping_from = None;
+                        trace!("day_04::records", "guard {} wakes at {} (slept {} minutes)", guard_num, timestamp, awake - asleep);
+                        for count in &mut g.slept_by_minute[asleep..awake] {
+                            *count += 1;
+                        }
+                    }
+                    else {
+                        return Err(line.error_in(event, "expected a guard to begin a shift, fall asleep or wake up"));
+                    }
+                }
+            }
+            else {
+                return Err(line.error_in(event, "no guard has begun a shift yet"));
+            }
+        }
+        Ok(guards)
+    }
+
+    // Every record on its own first, then the order they come in, which
+    // parsing checks once they're sorted
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let re = record_regex();
+        let event_re = Regex::new(r"^(Guard #\d+ begins shift|falls asleep|wakes up)$").unwrap();
+        let errors = lint::each_line(input, |line| {
+            let (_, _, event) = parse_record(&re, line)?;
+            if event_re.is_match(event) {
+                Ok(())
+            }
+            else {
+                Err(line.error_in(event, "expected a guard to begin a shift, fall asleep or wake up"))
+            }
+        });
+        if errors.is_empty() {
+            self.parse(input).err().into_iter().collect()
+        }
+        else {
+            errors
+        }
+    }
+
+    fn part1(&self, guards: &HashMap<i32,Guard>) -> Sleepiest {
+        let dummy = Guard::new();
+        let (id, guard) = guards.iter().fold((0, &dummy), |(id_max, guard_max), (&id, guard)| {
+            if guard_max.total_slept_min < guard.total_slept_min {
+                (id, guard)
+            }
+            else {
+                (id_max, guard_max)
+            }
+        });
+        verbose!("day_04", "guard {} slept for {} minutes", id, guard.total_slept_min);
+
+        let (max_minute, count) = guard.sleepiest_minute();
+        Sleepiest { strategy: Strategy::MostAsleep, id, minute: max_minute,
+                    total_asleep: guard.total_slept_min, times_on_minute: count }
+    }
+
+    fn part2(&self, guards: &HashMap<i32,Guard>) -> Sleepiest {
+        let (id, minute, count) = guards.iter().fold((0, 0, 0), |(id_max, minute_max, count_max), (&id, guard)| {
+            let (minute, count) = guard.sleepiest_minute();
+            if count_max < count {
+                (id, minute, count)
+            }
+            else {
+                (id_max, minute_max, count_max)
+            }
+        });
+        Sleepiest { strategy: Strategy::MostOften, id, minute,
+                    total_asleep: guards.get(&id).map_or(0, |guard| guard.total_slept_min), times_on_minute: count }
+    }
+
+    // A shift a night, each with a few naps, written out in a random order
+    // like the real input. Someone naps on the first night.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let date_fmt = "%F %H:%M";
+        let guards : Vec<i64> = (0..std::cmp::max(size / 4, 2)).map(|_| rng.between(10, 3500)).collect();
+        let mut night = NaiveDate::from_ymd_opt(1518, 1, 1).unwrap() + Duration::days(rng.between(0, 200));
+        let mut records = Vec::new();
+
+        for shift in 0..std::cmp::max(size, 1) {
+            let morning = night + Duration::days(1);
+            let start = if rng.chance(0.5) {
+                night.and_hms_opt(23, rng.between(45, 59) as u32, 0).unwrap()
+            }
+            else {
+                morning.and_hms_opt(0, rng.between(0, 10) as u32, 0).unwrap()
+            };
+            records.push(format!("[{}] Guard #{} begins shift", start.format(date_fmt), rng.pick(&guards)));
+
+            let mut minute = if start.date() == morning { start.minute() as i64 } else { 0 };
+            for _ in 0..rng.between(if shift == 0 { 1 } else { 0 }, 3) {
+                let asleep = minute + rng.between(1, 15);
+                let awake = asleep + rng.between(1, 15);
+                if awake > 59 {
+                    break;
+                }
+                records.push(format!("[{} 00:{:02}] falls asleep", morning.format("%F"), asleep));
+                records.push(format!("[{} 00:{:02}] wakes up", morning.format("%F"), awake));
+                minute = awake;
+            }
+            night = morning;
+        }
+
+        rng.shuffle(&mut records);
+        records.iter().map(|record| format!("{}\n", record)).collect()
+    }
+}
@@ -0,0 +1,117 @@
+extern crate aoc_common;
+
+use aoc_common::{trace, verbose, ParseError, Rng, Solution};
+
+// Whether two units are the same type with opposite polarities
+fn reacts(a: char, b: char) -> bool {
+    a != b && a.to_uppercase().next() == b.to_uppercase().next()
+}
+
+/// What's left of the polymer once every reaction has happened. Units go
+/// on a stack as they're read, and one that reacts with the top of the
+/// stack takes it off instead, so a single pass is enough.
+pub fn collapse(input: &[char]) -> Vec<char> {
+    let mut stack : Vec<char> = Vec::with_capacity(input.len());
+    for &unit in input {
+        match stack.last() {
+            Some(&top) if reacts(top, unit) => { stack.pop(); },
+            _ => stack.push(unit)
+        }
+    }
+    stack
+}
+
+/// The obviously correct way, kept to check `collapse` against.
+pub mod reference {
+    /// What's left of the polymer once every reaction has happened,
+    /// folding over it again and again until nothing more reacts.
+    pub fn collapse (input: &[char]) -> Vec<char> {
+        let mut cur : Vec<char> = input.to_vec();
+
+        loop {
+            // Cancel the polar opposites
+            let next = cur.iter().fold(Vec::<char>::new(),|mut l, &cur| {
+                if let Some(prev) = l.pop() {
+                    if prev != cur && prev.to_uppercase().next() == cur.to_uppercase().next() {
+                        return l;
+                    }
+                    l.push(prev);
+                    l.push(cur);
+                }
+                else {
+                    l.push(cur);
+                }
+                l
+            });
+
+            if next.len() != cur.len() {
+                cur = next;
+            }
+            else {
+                // If we didn't cancel any, we're done
+                return cur;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY : u32 = 5;
+
+    type Input = Vec<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<char>, ParseError> {
+        verbose!("day_05", "{} units", input.trim().len());
+        trace!("day_05", "input = {}", input.trim());
+        Ok(input.trim().chars().collect())
+    }
+
+    fn part1(&self, buf: &Vec<char>) -> usize {
+        collapse(buf).len()
+    }
+
+    fn part2(&self, buf: &Vec<char>) -> usize {
+        static ASCII_LOWER: [char;26] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i',
+                                         'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+                                         's', 't', 'u', 'v', 'w', 'x', 'y', 'z'];
+        let smallest = ASCII_LOWER.iter().fold(None as Option<Vec<char>>, |smallest, &c| {
+            let filtered : Vec<char> = buf.iter().filter_map(|&x| {
+                if x == c || x == c.to_uppercase().next().unwrap() {
+                    None
+                }
+                else {
+                    Some(x)
+                }
+            }).collect();
+            let cur = collapse(&filtered);
+            if let Some(ref s) = smallest {
+                if cur.len() < s.len() {
+                    Some(cur)
+                }
+                else {
+                    Some(s.to_vec())
+                }
+            }
+            else {
+                Some(cur)
+            }
+        });
+        smallest.unwrap().len()
+    }
+
+    // Units from a few types in both polarities, so that plenty react
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let types : Vec<char> = ('a'..='z').take(std::cmp::max(size / 100, 4).min(26)).collect();
+        let mut polymer : String = (0..std::cmp::max(size, 1)).map(|_| {
+            let unit = *rng.pick(&types);
+            if rng.chance(0.5) { unit.to_ascii_uppercase() } else { unit }
+        }).collect();
+        polymer.push('\n');
+        polymer
+    }
+}
@@ -0,0 +1,214 @@
+extern crate aoc_common;
+
+use std::collections::HashSet;
+use std::fmt;
+
+use aoc_common::{trace, verbose, Answer, Grid, Json, Param, Params, ParseError, Rng, Solution};
+
+fn dist(a: (i32,i32), b: (i32,i32)) -> i32 {
+    let ((ax,ay),(bx,by)) = (a,b);
+    (ax-bx).abs() + (ay-by).abs()
+}
+
+/// The index of the coordinate closest to `cell`, if only one is closest.
+pub fn closest(coords: &[(i32,i32)], cell: (i32,i32)) -> Option<usize> {
+    let mut owner = None;
+    let mut owner_dist = i32::MAX;
+    for (idx, coord) in coords.iter().enumerate() {
+        let d = dist(cell, *coord);
+        if d < owner_dist {
+            owner = Some(idx);
+            owner_dist = d;
+        }
+        else if d == owner_dist {
+            // a tie, unless something closer turns up
+            owner = None;
+        }
+    }
+    owner
+}
+
+/// The obviously correct way, kept to check `closest` against.
+pub mod reference {
+    use super::dist;
+
+    /// The index of the coordinate closest to `cell`, if only one is
+    /// closest, found by sorting them all by distance. Needs at least two
+    /// coordinates.
+    pub fn closest(coords: &[(i32,i32)], cell: (i32,i32)) -> Option<usize> {
+        let mut by_dist : Vec<(usize,i32)> = coords.iter().enumerate().map(|(idx, coord)| {
+            (idx, dist(cell, *coord))
+        }).collect();
+        by_dist.sort_by_key(|&(_, d)| d);
+
+        let (a, a_dist) = by_dist[0];
+        let (_, b_dist) = by_dist[1];
+        if a_dist != b_dist { Some(a) } else { None }
+    }
+
+    /// How many cells are within a total distance of `max_dist` of every
+    /// coordinate, found by adding up the distances from each one.
+    pub fn region_size(coords: &[(i32,i32)], max_dist: i32) -> usize {
+        let ((min_x, min_y), (max_x, max_y)) = super::extent(coords);
+        let xs = (max_x-max_dist)..=(min_x+max_dist);
+        let ys = (max_y-max_dist)..=(min_y+max_dist);
+        ys.flat_map(|y| xs.clone().map(move |x| (x,y))).filter( |cell| {
+            let sum_dist : i32 = coords.iter().map(|coord| { dist(*coord, *cell) }).sum();
+            sum_dist < max_dist
+        }).count()
+    }
+}
+
+/// How many cells are within a total distance of `max_dist` of every
+/// coordinate. The total splits into the distances along x and along y,
+/// so each column and row is added up once, and rows are counted against
+/// each column by a binary search.
+pub fn region_size(coords: &[(i32,i32)], max_dist: i32) -> usize {
+    let ((min_x, min_y), (max_x, max_y)) = extent(coords);
+    let x_dists : Vec<i32> = ((max_x-max_dist)..=(min_x+max_dist)).map(|x| {
+        coords.iter().map(|&(cx, _)| (cx - x).abs()).sum()
+    }).collect();
+    let mut y_dists : Vec<i32> = ((max_y-max_dist)..=(min_y+max_dist)).map(|y| {
+        coords.iter().map(|&(_, cy)| (cy - y).abs()).sum()
+    }).collect();
+    y_dists.sort_unstable();
+    x_dists.iter().map(|x_dist| y_dists.partition_point(|y_dist| x_dist + y_dist < max_dist)).sum()
+}
+
+// The box spanning every coordinate: ((min_x, min_y), (max_x, max_y))
+fn extent(coords: &[(i32,i32)]) -> ((i32,i32),(i32,i32)) {
+    let coords_sorted_x : Vec<(i32,i32)> = { let mut c = coords.to_vec(); c.sort_by(|(x0,_),(x1,_)| { x0.cmp(x1) }); c };
+    let coords_sorted_y : Vec<(i32,i32)> = { let mut c = coords.to_vec(); c.sort_by(|(_,y0),(_,y1)| { y0.cmp(y1) }); c };
+
+    let (min_x, _) = coords_sorted_x.first().unwrap();
+    let (max_x, _) = coords_sorted_x.last().unwrap();
+
+    let (_, min_y) = coords_sorted_y.first().unwrap();
+    let (_, max_y) = coords_sorted_y.last().unwrap();
+
+    ((*min_x, *min_y), (*max_x, *max_y))
+}
+
+/// The largest area that isn't infinite, unless every area is.
+pub struct LargestArea(Option<usize>);
+
+impl fmt::Display for LargestArea {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(area) => write!(fmt, "{}", area),
+            None => write!(fmt, "none")
+        }
+    }
+}
+
+impl Answer for LargestArea {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        match self.0 {
+            Some(area) => vec![("area", area.into())],
+            None => vec![("finite_areas", 0.into())]
+        }
+    }
+
+    fn explain(&self) -> Vec<String> {
+        match self.0 {
+            Some(area) => vec![format!("the largest finite area is {} cells", area)],
+            None => vec!["every area reaches the edge, so none is finite".to_string()]
+        }
+    }
+}
+
+const MAX_DIST : Param = Param {
+    name: "max_dist",
+    default: 10000,
+    min: 1,
+    max: 100_000,
+    about: "part 2's limit on the total distance to every coordinate"
+};
+
+#[derive(Default)]
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY : u32 = 6;
+
+    const PARAMS : &'static [Param] = &[MAX_DIST];
+
+    // The coordinates, and the total distance part 2's region stays under
+    type Input = (Vec<(i32,i32)>, i32);
+    type Answer1 = LargestArea;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<(Vec<(i32,i32)>, i32), ParseError> {
+        self.parse_with(input, &Params::default())
+    }
+
+    fn parse_with(&self, input: &str, params: &Params) -> Result<(Vec<(i32,i32)>, i32), ParseError> {
+        let coords = aoc_common::lines(input).map(|line| {
+            let coords = line.text.split(", ").map(|x| {
+                line.parse::<i32>(x, "a coordinate")
+            }).collect::<Result<Vec<i32>, ParseError>>()?;
+            if coords.len() != 2 {
+                return Err(line.error("expected a coordinate pair such as 1, 6"));
+            }
+            Ok((coords[0], coords[1]))
+        }).collect::<Result<Vec<(i32,i32)>, ParseError>>()?;
+        if coords.is_empty() {
+            return Err(ParseError::at_end(input, "expected at least one coordinate"));
+        }
+        let max_dist = params.value(&MAX_DIST).map_err(|e| ParseError::at_end(input, &e))?;
+        Ok((coords, max_dist))
+    }
+
+    fn part1(&self, (coords, _): &(Vec<(i32,i32)>, i32)) -> LargestArea {
+        let ((min_x, min_y), (max_x, max_y)) = extent(coords);
+
+        verbose!("day_06", "bounding box = [({},{}),({},{})]", min_x, min_y, max_x, max_y);
+
+        // Which coordinate owns each cell of the bounding box
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let owners = Grid::from_fn(width, height, |(x, y)| {
+            closest(coords, (min_x + x as i32, min_y + y as i32))
+        });
+
+        let mut areas = vec![0; coords.len()];
+        let mut infinite = HashSet::new();
+        for ((x, y), owner) in owners.iter() {
+            trace!("day_06::owners", "({},{}) -> {:?}", min_x + x as i32, min_y + y as i32, owner);
+            if let Some(owner) = *owner {
+                areas[owner] += 1;
+                // disqualify coordinates with areas touching the edge
+                if x == 0 || x == width - 1 || y == 0 || y == height - 1 {
+                    infinite.insert(owner);
+                }
+            }
+        }
+
+        verbose!("day_06", "{} of {} areas are infinite", infinite.len(), coords.len());
+        LargestArea(areas.into_iter().enumerate().filter(|(owner, _)| {
+            !infinite.contains(owner)
+        }).map(|(_, area)| area).max())
+    }
+
+    fn part2(&self, &(ref coords, max_dist): &(Vec<(i32,i32)>, i32)) -> usize {
+        region_size(coords, max_dist)
+    }
+
+    // Scattered coordinates, with one in the middle of a cross of four
+    // others so that at least one area is finite
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let side = 20 + 4 * size as i64;
+        let (center, arm) = (side / 2, rng.between(2, 10));
+        let mut coords = vec![(center, center),
+                              (center - arm, center), (center + arm, center),
+                              (center, center - arm), (center, center + arm)];
+        while coords.len() < std::cmp::max(size, coords.len()) {
+            let coord = (rng.between(0, side), rng.between(0, side));
+            if !coords.contains(&coord) {
+                coords.push(coord);
+            }
+        }
+        rng.shuffle(&mut coords);
+        coords.iter().map(|(x, y)| format!("{}, {}\n", x, y)).collect()
+    }
+}
@@ -0,0 +1,220 @@
+extern crate aoc_common;
+extern crate regex;
+
+use std::collections::HashSet;
+
+use aoc_common::lint;
+use aoc_common::{Line, Param, Params, ParseError, Rng, Solution};
+use regex::Regex;
+
+const NUM_WORKERS : Param = Param {
+    name: "workers",
+    default: 5,
+    min: 1,
+    max: 26,
+    about: "how many workers part 2 has"
+};
+const TIME_OFFSET : Param = Param {
+    name: "offset",
+    default: 60,
+    min: 0,
+    max: 3600,
+    about: "the seconds every step takes on top of its letter"
+};
+
+// Runs the steps on `num_workers` workers, each step taking its letter's
+// position in the alphabet plus `time_offset` seconds. Returns the order
+// the steps finished in and the total time taken.
+fn schedule(requirements: &[(char, char)], num_workers: usize, time_offset: usize) -> (String, usize) {
+    let letters : Vec<char> = (b'A' ..= b'Z').map(|c| { c as char}).collect();
+
+    let mut pending_letters : HashSet<char> = HashSet::new();
+    let mut dependencies : Vec<HashSet<char>> = letters.iter().map(|_| { HashSet::<char>::new() }).collect();
+
+    for &(required, step) in requirements {
+        pending_letters.insert(step);
+        pending_letters.insert(required);
+        let step_num = step as usize - b'A' as usize;
+
+        dependencies[step_num].insert(required);
+    }
+
+    let mut order = Vec::<char>::new();
+    let mut total_time = 0;
+
+    let mut workers : Vec<Option<(char,usize)>> = vec![None; num_workers];
+    let mut waiting_letters : HashSet<char> = pending_letters.clone();
+
+    while !pending_letters.is_empty() {
+        let can_place = workers.iter().any(|x| x.is_none());
+        let mut did_place = false;
+        if can_place {
+            for &letter in letters.iter() {
+                let idx = letter as usize - b'A' as usize;
+                if waiting_letters.contains(&letter)
+                    && dependencies[idx].is_empty() {
+                        for worker in &mut workers {
+                            if worker.is_none() {
+                                let time = letter as usize - b'A' as usize + 1 + time_offset;
+                                *worker = Some((letter, time));
+                                waiting_letters.remove(&letter);
+                                did_place = true;
+                                break;
+                            }
+                        }
+                    }
+            }
+        }
+        if !did_place {
+            // Nothing could start, so something had better be running
+            if workers.iter().all(Option::is_none) {
+                let mut stuck : Vec<char> = waiting_letters.into_iter().collect();
+                stuck.sort();
+                panic!("steps {} can never start", stuck.into_iter().collect::<String>());
+            }
+              // do work
+            total_time += 1;
+            for worker in &mut workers {
+                if let Some((l, time)) = *worker {
+                    let time = time - 1;
+                    if time == 0 {
+                        pending_letters.remove(&l);
+                        order.push(l);
+                        for deps in dependencies.iter_mut() {
+                            deps.remove(&l);
+                        }
+                        *worker = None
+                    }
+                    else {
+                        *worker = Some((l, time));
+                    }
+                }
+            }
+        }
+    }
+    (order.iter().collect(), total_time)
+}
+
+/// The (required, step) pairs, and how part 2 runs them.
+pub struct Instructions {
+    requirements: Vec<(char, char)>,
+    num_workers: usize,
+    time_offset: usize,
+}
+
+fn requirement_regex() -> Regex {
+    Regex::new(r"^Step ([A-Z]) must be finished before step ([A-Z]) can begin\.$").unwrap()
+}
+
+// The requirements on a cycle, by index: the step each one holds up leads
+// back round to the step it waits for
+fn cycles(requirements: &[(char, char)]) -> Vec<usize> {
+    let reaches = |from: char, to: char| {
+        let mut seen = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(step) = stack.pop() {
+            if step == to {
+                return true;
+            }
+            if seen.insert(step) {
+                stack.extend(requirements.iter().filter(|(required, _)| *required == step).map(|&(_, next)| next));
+            }
+        }
+        false
+    };
+    (0..requirements.len()).filter(|&i| reaches(requirements[i].1, requirements[i].0)).collect()
+}
+
+fn cycle_error(line: Line, (required, step): (char, char)) -> ParseError {
+    line.error(&format!("{} and {} wait on each other through a cycle of requirements", required, step))
+}
+
+// The step required, and the step that waits for it
+fn parse_requirement(re: &Regex, line: Line) -> Result<(char, char), ParseError> {
+    let captures = re.captures(line.text).ok_or_else(|| {
+        line.error("expected a line such as: Step C must be finished before step A can begin.")
+    })?;
+    Ok((captures[1].chars().next().unwrap(), captures[2].chars().next().unwrap()))
+}
+
+#[derive(Default)]
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY : u32 = 7;
+
+    const PARAMS : &'static [Param] = &[NUM_WORKERS, TIME_OFFSET];
+
+    type Input = Instructions;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Instructions, ParseError> {
+        self.parse_with(input, &Params::default())
+    }
+
+    fn parse_with(&self, input: &str, params: &Params) -> Result<Instructions, ParseError> {
+        let re = requirement_regex();
+        let lines : Vec<Line> = aoc_common::lines(input).collect();
+        let requirements = lines.iter().map(|&line| {
+            parse_requirement(&re, line)
+        }).collect::<Result<Vec<(char, char)>, ParseError>>()?;
+        if let Some(&i) = cycles(&requirements).first() {
+            return Err(cycle_error(lines[i], requirements[i]));
+        }
+        let param = |param| params.value(param).map_err(|e| ParseError::at_end(input, &e));
+        Ok(Instructions {
+            requirements,
+            num_workers: param(&NUM_WORKERS)?,
+            time_offset: param(&TIME_OFFSET)?
+        })
+    }
+
+    // Every line is a requirement, and none of them go round in a circle,
+    // which would leave steps that can never start
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let re = requirement_regex();
+        let mut requirements = Vec::new();
+        let mut lines = Vec::new();
+        let errors = lint::each_line(input, |line| {
+            requirements.push(parse_requirement(&re, line)?);
+            lines.push(line);
+            Ok(())
+        });
+        if !errors.is_empty() {
+            return errors;
+        }
+        cycles(&requirements).into_iter().map(|i| cycle_error(lines[i], requirements[i])).collect()
+    }
+
+    fn part1(&self, instructions: &Instructions) -> String {
+        // A single worker with no overhead finishes the steps in order
+        let (order, _) = schedule(&instructions.requirements, 1, 0);
+        order
+    }
+
+    fn part2(&self, instructions: &Instructions) -> usize {
+        let (_, total_time) = schedule(&instructions.requirements, instructions.num_workers, instructions.time_offset);
+        total_time
+    }
+
+    // The steps in a random order, each depending on at least one step
+    // before it so that they are all connected and there are no cycles
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut steps : Vec<char> = ('A'..='Z').take(std::cmp::max(size, 2).min(26)).collect();
+        rng.shuffle(&mut steps);
+
+        let mut requirements = Vec::new();
+        for i in 1..steps.len() {
+            let mut required : Vec<char> = (0..rng.between(1, 3)).map(|_| steps[rng.below(i)]).collect();
+            required.sort();
+            required.dedup();
+            requirements.extend(required.into_iter().map(|required| (required, steps[i])));
+        }
+
+        rng.shuffle(&mut requirements);
+        requirements.iter().map(|(required, step)| {
+            format!("Step {} must be finished before step {} can begin.\n", required, step)
+        }).collect()
+    }
+}
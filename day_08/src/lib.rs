@@ -0,0 +1,161 @@
+extern crate aoc_common;
+
+use std::iter::Iterator;
+
+use aoc_common::{ParseError, Rng, Solution};
+
+// Children , Metadata, Value
+pub struct Node (Vec<Node>, Vec<u32>, u32);
+
+// Dropping children one level at a time, since a tree can be deeper than
+// the stack
+impl Drop for Node {
+    fn drop(&mut self) {
+        let mut orphans = std::mem::take(&mut self.0);
+        while let Some(mut node) = orphans.pop() {
+            orphans.append(&mut node.0);
+        }
+    }
+}
+
+struct NodeIter<'a>(Vec<Box<dyn Iterator<Item=&'a Node> + 'a>>);
+
+impl<'a> NodeIter<'a> {
+    fn new(node : &'a Node) -> NodeIter<'a> {
+        NodeIter(vec![Box::new(vec![node].into_iter())])
+    }
+}
+
+impl<'a> Iterator for NodeIter<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        // pre-order
+        let NodeIter(ref mut stack) = self;
+        loop {
+            if let Some(mut top) = stack.pop() {
+                // top is a boxed iterator
+                if let Some(node) = top.next() {
+                    let Node(children, _, _) = node;
+                    stack.push(top);
+                    stack.push(Box::new(children.iter()));
+                    return Some(node);
+                }            
+            }
+            else {
+                return None;
+            }
+        } 
+    }
+}
+
+const TRUNCATED : &str = "the tree ends before all of its nodes are complete";
+
+// A node's value: the sum of its entries if it has no children, or else
+// of the values of the children its entries number from 1. None if that
+// doesn't fit in a u32.
+fn value(children: &[Node], entries: &[u32]) -> Option<u32> {
+    entries.iter().try_fold(0u32, |sum, &entry| {
+        if children.is_empty() {
+            sum.checked_add(entry)
+        }
+        else {
+            let child = (entry as usize).checked_sub(1).and_then(|idx| children.get(idx));
+            sum.checked_add(child.map_or(0, |Node(_, _, value)| *value))
+        }
+    })
+}
+
+// A node whose children are still being read
+struct Pending {
+    num_children: u32,
+    num_entries: u32,
+    children: Vec<Node>
+}
+
+// Reads the tree from its numbers. Nodes can nest as deep as the input
+// likes, so this keeps its own stack of them rather than recursing.
+fn read_tree<I: Iterator<Item = u32>> (input: &mut I) -> Result<Node, &'static str>
+{
+    let mut pending : Vec<Pending> = Vec::new();
+    loop {
+        let num_children = input.next().ok_or(TRUNCATED)?;
+        let num_entries = input.next().ok_or(TRUNCATED)?;
+        pending.push(Pending { num_children, num_entries, children: Vec::new() });
+
+        // Every node with all its children read is followed by its entries
+        while pending.last().is_some_and(|node| node.children.len() == node.num_children as usize) {
+            let Pending { num_entries, children, .. } = pending.pop().unwrap();
+            let entries = (0..num_entries).map(|_| input.next().ok_or(TRUNCATED)).collect::<Result<Vec<u32>, _>>()?;
+            let value = value(&children, &entries).ok_or("a node's value doesn't fit in a u32")?;
+            let node = Node(children, entries, value);
+            match pending.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => return Ok(node)
+            }
+        }
+    }
+}
+
+// Writes out a node with up to `budget` nodes below it, taking them off the
+// budget. Entries can refer to children that aren't there, as in the puzzle.
+fn generate_node(rng: &mut Rng, budget: &mut usize, nums: &mut Vec<u32>) {
+    let num_children = rng.between(0, std::cmp::min(*budget, 5) as i64) as usize;
+    *budget -= num_children;
+    let num_entries = rng.between(1, 3) as usize;
+    nums.push(num_children as u32);
+    nums.push(num_entries as u32);
+    for _ in 0..num_children {
+        generate_node(rng, budget, nums);
+    }
+    for _ in 0..num_entries {
+        nums.push(rng.between(1, 9) as u32);
+    }
+}
+
+#[derive(Default)]
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY : u32 = 8;
+
+    type Input = Node;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Node, ParseError> {
+        let mut nums = Vec::new();
+        for line in aoc_common::lines(input) {
+            for s in line.text.split_whitespace() {
+                nums.push(line.parse::<u32>(s, "a number")?);
+            }
+        }
+
+        // num_children, children, num_entries, entries
+        read_tree(&mut nums.into_iter()).map_err(|message| ParseError::at_end(input, message))
+    }
+
+    fn part1(&self, tree: &Node) -> u32 {
+        let tree_iter = NodeIter::new(tree);
+        let mut sum = 0;
+        for Node(_, entries, _) in tree_iter {
+            for e in entries.iter() {
+                sum += e;
+            }
+        }
+        sum
+    }
+
+    fn part2(&self, tree: &Node) -> u32 {
+        let Node(_, _, root_value) = tree;
+        *root_value
+    }
+
+    // A tree of about `size` nodes
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let (mut nums, mut budget) = (Vec::new(), size);
+        generate_node(rng, &mut budget, &mut nums);
+        let nums : Vec<String> = nums.iter().map(|num| num.to_string()).collect();
+        format!("{}\n", nums.join(" "))
+    }
+}
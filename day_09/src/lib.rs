@@ -0,0 +1,145 @@
+extern crate aoc_common;
+#[macro_use]
+extern crate intrusive_collections;
+use aoc_common::{trace, InputShape, ParseError, Rng, Solution};
+use intrusive_collections::{LinkedList, LinkedListLink};
+use intrusive_collections::linked_list::CursorMut;
+use std::cell::Cell;
+
+struct Node {
+    link: LinkedListLink,
+    value: Cell<u32>,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        self.value == other.value
+    }
+}
+
+intrusive_adapter!(NodeAdapter = Box<Node>: Node { link: LinkedListLink });
+
+// The marbles from the front of the list, with the current one in
+// parentheses
+fn circle(cursor: &CursorMut<NodeAdapter>) -> String {
+    let current = cursor.as_cursor();
+    let mut walk = current.clone();
+    while !walk.is_null() {
+        walk.move_prev();
+    }
+    walk.move_next();
+
+    let mut marbles = Vec::new();
+    while let Some(node) = walk.get() {
+        if current.get() == Some(node) {
+            marbles.push(format!("({})", node.value.get()));
+        }
+        else {
+            marbles.push(node.value.get().to_string());
+        }
+        walk.move_next();
+    }
+    marbles.join(" ")
+}
+
+// How many times as many marbles part 2 plays with
+const PART_2_SCALE : u32 = 100;
+// The most players a game can have, so their scores fit in memory
+const MAX_PLAYERS : usize = 1_000_000;
+
+// Plays the marble game and returns the winning score
+fn high_score(num_players: usize, max_value: u32) -> u64 {
+    let mut stones = LinkedList::new(NodeAdapter::new());
+
+    let mut scores = vec![0u64; num_players];
+    let mut cur_player = 0;
+
+    let mut cursor = stones.cursor_mut();
+
+    let b = Box::new(Node {
+        link: LinkedListLink::new(),
+        value: Cell::new(0),
+    });
+    cursor.insert_after(b);
+
+    for value in 1..=max_value {
+        if value % 23 != 0 {
+            cursor.move_next();
+            if cursor.is_null() { cursor.move_next() };
+            let v = Box::new(Node {
+                link: LinkedListLink::new(),
+                value: Cell::new(value),
+            });
+            cursor.insert_after(v);
+            cursor.move_next();
+        }
+        else {
+            scores[cur_player] += value as u64;
+            for _ in 0..7 {
+                cursor.move_prev();
+                if cursor.is_null() { cursor.move_prev(); }                
+            }
+            scores[cur_player] += cursor.get().unwrap().value.get() as u64;
+            cursor.remove();
+            if cursor.is_null() { cursor.move_next(); }
+        }
+        cur_player = (cur_player + 1) % num_players;
+        trace!("day_09::circle", "[{:02}] {}", value, circle(&cursor));
+    }
+    scores.iter().skip(1).fold(scores[0], |max, &x| { std::cmp::max(max, x) })
+}
+
+#[derive(Default)]
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY : u32 = 9;
+    const INPUT : InputShape = InputShape::Values(&["num_players", "max_value"]);
+
+    // (num_players, max_value)
+    type Input = (usize, u32);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<(usize, u32), ParseError> {
+        let line = aoc_common::lines(input).next().ok_or_else(|| {
+            ParseError::at_end(input, "expected <num_players> <max_value>")
+        })?;
+        let mut args = line.text.split(' ');
+        let players_arg = args.next().unwrap();
+        let num_players = line.parse::<usize>(players_arg, "an unsigned int for num_players")?;
+        if num_players == 0 {
+            return Err(line.error_in(players_arg, "expected at least one player"));
+        }
+        if num_players > MAX_PLAYERS {
+            return Err(line.error_in(players_arg, &format!("expected at most {} players", MAX_PLAYERS)));
+        }
+        let max_value = match args.next() {
+            Some(arg) => {
+                let max_value = line.parse::<u32>(arg, "an unsigned int for max_value")?;
+                if max_value.checked_mul(PART_2_SCALE).is_none() {
+                    return Err(line.error_in(arg, &format!("expected a max_value of at most {}, for part 2's {} times as many",
+                                                           u32::MAX / PART_2_SCALE, PART_2_SCALE)));
+                }
+                max_value
+            },
+            None => return Err(line.error("expected <num_players> <max_value>"))
+        };
+        Ok((num_players, max_value))
+    }
+
+    fn part1(&self, &(num_players, max_value): &(usize, u32)) -> u64 {
+        high_score(num_players, max_value)
+    }
+
+    fn part2(&self, &(num_players, max_value): &(usize, u32)) -> u64 {
+        let max_value = max_value.checked_mul(PART_2_SCALE).expect("max_value is checked as it's parsed");
+        high_score(num_players, max_value)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let num_players = rng.between(2, 9 + size as i64);
+        let max_value = rng.between(1, 100 * std::cmp::max(size, 1) as i64);
+        format!("{} {}", num_players, max_value)
+    }
+}
@@ -0,0 +1,247 @@
+extern crate aoc_common;
+extern crate regex;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+use std::thread;
+
+use aoc_common::lint;
+use aoc_common::{Answer, Grid, Json, Line, ParseError, Rng, Solution};
+use aoc_common::viz::{Backend, Frame, Kind};
+use regex::Regex;
+
+#[derive(Clone)]
+pub struct Vec2 { x: i32, y: i32 }
+
+#[derive(Clone)]
+pub struct Particle { position: Vec2, velocity: Vec2 }
+
+impl Particle {
+    fn step(&mut self, n: i32) {
+        self.position.x += self.velocity.x * n;
+        self.position.y += self.velocity.y * n;
+    }
+}
+
+// Draws from min,min to max,max, scaled to fit the screen
+fn particles_frame(particles: &[Particle], min: &Vec2, max: &Vec2) -> Frame {
+    let mut set = HashSet::<(i32,i32)>::new();
+    let xscale = 120;
+    let yscale = 20;
+    
+    
+    for p in particles {
+        let x_interp = (p.position.x - min.x) as f32 / (max.x - min.x) as f32;
+        let x = (x_interp * xscale as f32) as i32;
+        let y_interp = (p.position.y - min.y) as f32 / (max.y - min.y) as f32;
+        let y = (y_interp * yscale as f32) as i32;
+        
+        set.insert((x, y));
+    }
+    Frame::new(Grid::from_fn(xscale as usize + 1, yscale as usize + 1, |(x, y)| {
+        if set.contains(&(x as i32, y as i32)) { '*' } else { ' ' }
+    })).with_palette(|c| if c == '*' { Kind::Particle } else { Kind::Empty })
+}
+
+fn find_bounds(particles: &[Particle]) -> (Vec2,Vec2) {
+    let mut min = Vec2{x: i32::MAX, y: i32::MAX};
+    let mut max = Vec2{x: i32::MIN, y: i32::MIN};
+
+    for p in particles {
+        min.x = std::cmp::min(min.x, p.position.x);
+        min.y = std::cmp::min(min.y, p.position.y);
+        max.x = std::cmp::max(max.x, p.position.x);
+        max.y = std::cmp::max(max.y, p.position.y);
+    }
+    (min, max)
+}
+
+fn move_particles(particles: &mut [Particle], num_steps: i32) {
+    for p in particles {
+        p.step(num_steps);
+    }
+}
+
+// Steps the particles until their bounding box stops shrinking, which is
+// when they spell out the message. Returns the number of steps taken.
+// The box is measured by its width plus its height: each of those only
+// ever shrinks and then grows, so the sum does too, and it stops
+// shrinking at its smallest even if the particles all move together.
+fn converge(particles: &mut [Particle]) -> i32 {
+    let size = |particles: &[Particle]| {
+        let (min, max) = find_bounds(particles);
+        (max.x as i64 - min.x as i64) + (max.y as i64 - min.y as i64)
+    };
+    let mut num_steps = 0;
+    let mut cur_size = size(particles);
+    loop {
+        move_particles(particles, 1);
+        let next_size = size(particles);
+        if next_size >= cur_size {
+            move_particles(particles, -1);
+            return num_steps;
+        }
+        cur_size = next_size;
+        num_steps += 1;
+    }
+}
+
+/// The particles as they are drawn in the sky.
+pub struct Message(Vec<String>);
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Message(rows) = self;
+        for row in rows {
+            write!(f, "\n{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+impl Answer for Message {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        let Message(rows) = self;
+        vec![("rows", Json::Array(rows.iter().map(|row| row.as_str().into()).collect()))]
+    }
+}
+
+// The most cells a message can take up. Puzzle messages take a few
+// hundred.
+const MAX_MESSAGE_CELLS : i64 = 1_000_000;
+
+fn read_message(particles: &[Particle]) -> Message {
+    let (min, max) = find_bounds(particles);
+    let (width, height) = (max.x as i64 - min.x as i64 + 1, max.y as i64 - min.y as i64 + 1);
+    if width * height > MAX_MESSAGE_CELLS {
+        panic!("the particles are at their closest in a {}x{} box, too spread out to read", width, height);
+    }
+    let set : HashSet<(i32,i32)> = particles.iter().map(|p| (p.position.x, p.position.y)).collect();
+    Message((min.y..=max.y).map(|y| {
+        (min.x..=max.x).map(|x| if set.contains(&(x,y)) { '#' } else { '.' }).collect()
+    }).collect())
+}
+
+/// Shows the particles drifting together, up to the step where they
+/// spell out the message, then waits for a key.
+pub fn animate(particles: &[Particle], backend: &mut dyn Backend) {
+    let mut particles = particles.to_vec();
+    let message_step = converge(&mut particles.clone());
+
+    let (min, max) = find_bounds(&particles);
+    let initial = format!("initial bounds: ({}, {})", max.x - min.x, max.y - min.y);
+    if max.x - min.x <= 250 {
+        backend.draw(&particles_frame(&particles, &min, &max).with_text(&initial));
+    }
+
+    let mut num_steps = 0;
+    let mut stepsize = 100;
+    while num_steps < message_step {
+        let (min,max) = find_bounds(&particles);
+        if max.x - min.x <= 200 {
+            stepsize = 1;
+        }
+        stepsize = std::cmp::min(stepsize, message_step - num_steps);
+        let bounds = format!("{}: ({}, {})", num_steps, max.x - min.x, max.y - min.y);
+        move_particles(&mut particles, stepsize);
+        num_steps += stepsize;
+        backend.draw(&particles_frame(&particles,&min,&max).with_text(&bounds));
+        if stepsize == 1 {
+            thread::sleep(Duration::from_millis(1000))
+        }
+    }
+    backend.wait();
+    backend.finish();
+}
+
+fn particle_regex() -> Regex {
+    Regex::new(r"position=<\s*([^,]+),\s*([^>]+)> velocity=<\s*([^,]+),\s*([^>]+)>").unwrap()
+}
+
+// How far out particles can start, and how fast they can go. The box
+// around them shrinks by at least one every step until they converge, so
+// with these no particle can travel out of i32 on the way.
+const MAX_POSITION : i32 = 1_000_000;
+const MAX_VELOCITY : i32 = 100;
+
+fn parse_particle(re: &Regex, line: Line) -> Result<Particle, ParseError> {
+    let captures = re.captures(line.text).ok_or_else(|| {
+        line.error("expected a line such as: position=< 9,  1> velocity=< 0,  2>")
+    })?;
+    let num = |i: usize, limit: i32, what: &str| {
+        let text = captures.get(i).unwrap().as_str().trim();
+        let value = line.parse::<i32>(text, "a number")?;
+        if !(-limit..=limit).contains(&value) {
+            return Err(line.error_in(text, &format!("expected a {} between -{} and {}", what, limit, limit)));
+        }
+        Ok(value)
+    };
+    let position = Vec2{ x: num(1, MAX_POSITION, "position")?, y: num(2, MAX_POSITION, "position")? };
+    let velocity = Vec2{ x: num(3, MAX_VELOCITY, "velocity")?, y: num(4, MAX_VELOCITY, "velocity")? };
+    Ok(Particle{position, velocity})
+}
+
+#[derive(Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY : u32 = 10;
+
+    type Input = Vec<Particle>;
+    type Answer1 = Message;
+    type Answer2 = i32;
+
+    // Particles that all move together would never come any closer
+    fn parse(&self, input: &str) -> Result<Vec<Particle>, ParseError> {
+        let re = particle_regex();
+        let particles = aoc_common::lines(input).map(|line| parse_particle(&re, line))
+            .collect::<Result<Vec<Particle>, ParseError>>()?;
+        let velocities : HashSet<(i32,i32)> = particles.iter().map(|p| (p.velocity.x, p.velocity.y)).collect();
+        if velocities.len() < 2 {
+            return Err(ParseError::at_end(input, "expected particles moving at different velocities"));
+        }
+        Ok(particles)
+    }
+
+    // Every line is a particle, and then whatever parsing finds
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let re = particle_regex();
+        let errors = lint::each_line(input, |line| parse_particle(&re, line).map(|_| ()));
+        if errors.is_empty() {
+            self.parse(input).err().into_iter().collect()
+        }
+        else {
+            errors
+        }
+    }
+
+    fn part1(&self, particles: &Vec<Particle>) -> Message {
+        let mut particles = particles.clone();
+        converge(&mut particles);
+        read_message(&particles)
+    }
+
+    fn part2(&self, particles: &Vec<Particle>) -> i32 {
+        converge(&mut particles.clone())
+    }
+
+    // Points of a message in a small box, each set moving away from it.
+    // Running them backwards brings them back together.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let (width, height) = (6 + size as i64, 10);
+        let steps = rng.between(10, 100 * size.clamp(1, 1_000) as i64);
+        let mut lines = Vec::new();
+        for _ in 0..std::cmp::max(size, 4) {
+            let (x, y) = (rng.between(0, width - 1), rng.between(0, height - 1));
+            let velocity = |rng: &mut Rng| {
+                let speed = rng.between(1, 5);
+                if rng.chance(0.5) { speed } else { -speed }
+            };
+            let (dx, dy) = (velocity(rng), velocity(rng));
+            lines.push(format!("position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>\n",
+                               x - dx * steps, y - dy * steps, dx, dy));
+        }
+        lines.concat()
+    }
+}
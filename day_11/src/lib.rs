@@ -0,0 +1,153 @@
+extern crate aoc_common;
+
+use std::fmt;
+
+use aoc_common::{Answer, Grid, InputShape, Json, ParseError, Rng, Solution};
+
+const GRID_SIZE : usize = 300;
+
+pub struct Cell (pub u32, pub u32);
+
+/// The power level of the fuel cell at `cell`, counting from 1.
+pub fn cell_level(cell: Cell, serial: i32) -> i8 {
+    let Cell(x, y) = cell;
+    // Wide enough for any serial number
+    let rack_id = x as i64 + 10;
+    let mut level = rack_id * y as i64;
+    level += serial as i64;
+    level *= rack_id;
+    
+    (((level / 100) % 10) - 5) as i8
+}
+
+/// Running totals of the power levels, so that a square's power takes
+/// four lookups rather than adding up every cell in it. Cell `(x, y)`
+/// holds the power of every fuel cell above and to the left of it,
+/// counting from 1, so the top row and left column are 0.
+pub struct Sums(Grid<i32>);
+
+impl Sums {
+    pub fn new(levels: &Grid<i8>) -> Sums {
+        let mut sums = Grid::new(levels.width() + 1, levels.height() + 1, 0);
+        for y in 1..=levels.height() {
+            for x in 1..=levels.width() {
+                sums[(x, y)] = levels[(x - 1, y - 1)] as i32
+                    + sums[(x - 1, y)] + sums[(x, y - 1)] - sums[(x - 1, y - 1)];
+            }
+        }
+        Sums(sums)
+    }
+
+    /// The power of the square of side `size` with its top left at `cell`.
+    pub fn square_power(&self, cell: Cell, size: u32) -> i32 {
+        let Sums(sums) = self;
+        let Cell(x, y) = cell;
+        let (left, top) = ((x - 1) as usize, (y - 1) as usize);
+        let (right, bottom) = (left + size as usize, top + size as usize);
+        sums[(right, bottom)] - sums[(left, bottom)] - sums[(right, top)] + sums[(left, top)]
+    }
+}
+
+/// The obviously correct way, kept to check `Sums` against.
+pub mod reference {
+    use aoc_common::Grid;
+
+    use super::Cell;
+
+    /// The power of the square of side `size` with its top left at `cell`,
+    /// adding up every fuel cell in it.
+    pub fn square_power(levels: &Grid<i8>, cell: Cell, size: u32) -> i32 {
+        let mut power : i32 = 0;
+        let Cell(x, y) = cell;
+        for j in 0..size {
+            for i in 0..size {
+                power += levels[((x + i - 1) as usize, (y + j - 1) as usize)] as i32;
+            }
+        }
+        power
+    }
+}
+
+/// The top-left corner and size of a square.
+pub struct Square(Cell, u32);
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Square(Cell(x, y), size) = self;
+        write!(f, "{},{},{}", x, y, size)
+    }
+}
+
+impl Answer for Square {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        let Square(Cell(x, y), size) = self;
+        vec![("x", (*x).into()), ("y", (*y).into()), ("size", (*size).into())]
+    }
+}
+
+// The square with the most power among all squares of the given sizes
+fn max_square<I: Iterator<Item = u32>>(levels: &Grid<i8>, sizes: I) -> Square {
+    let sums = Sums::new(levels);
+    let mut max_power : Option<i32> = None;
+    let mut max_cell : Option<Cell> = None;
+    let mut max_size : Option<u32> = None;
+
+    for size in sizes {
+        for j in 1..=(GRID_SIZE as u32 + 1 - size) {
+            for i in 1..=(GRID_SIZE as u32 + 1 - size) {
+                let power = sums.square_power(Cell(i,j), size);
+                if let Some(max_pow) = max_power {
+                    if power > max_pow {
+                        max_power = Some(power);
+                        max_cell = Some(Cell(i,j));
+                        max_size = Some(size);
+                    }
+                }
+                else {
+                    max_power = Some(power);
+                    max_cell = Some(Cell(i,j));
+                    max_size = Some(size);
+                }
+            }
+        }
+    }
+    Square(max_cell.expect("Didn't find a max power?!"), max_size.unwrap())
+}
+
+#[derive(Default)]
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY : u32 = 11;
+    const INPUT : InputShape = InputShape::Values(&["grid_serial"]);
+
+    /// The power level of every fuel cell in the grid.
+    type Input = Grid<i8>;
+    type Answer1 = String;
+    type Answer2 = Square;
+
+    fn parse(&self, input: &str) -> Result<Grid<i8>, ParseError> {
+        let line = aoc_common::lines(input).next().ok_or_else(|| {
+            ParseError::at_end(input, "expected <grid_serial>")
+        })?;
+        let serial = line.parse::<i32>(line.text.trim(), "an int for grid_serial")?;
+        Ok(Grid::from_fn(GRID_SIZE, GRID_SIZE, |(x, y)| {
+            cell_level(Cell(x as u32 + 1, y as u32 + 1), serial)
+        }))
+    }
+
+    fn part1(&self, levels: &Grid<i8>) -> String {
+        // run a kernel over all 3x3 squares
+        let Square(Cell(x, y), _) = max_square(levels, 3..=3);
+        format!("{},{}", x, y)
+    }
+
+    fn part2(&self, levels: &Grid<i8>) -> Square {
+        max_square(levels, 1..=(GRID_SIZE as u32))
+    }
+
+    // Any serial number works; the grid is always the same size
+    fn generate(&self, rng: &mut Rng, _size: usize) -> String {
+        rng.between(1, 9999).to_string()
+    }
+}
@@ -0,0 +1,355 @@
+extern crate aoc_common;
+extern crate regex;
+
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use aoc_common::{trace, verbose, Answer, Grid, Json, Param, Params, ParseError, Rng, Solution};
+use aoc_common::viz::{Backend, Frame, Kind};
+use regex::Regex;
+
+const PART_1_STEPS : Param = Param {
+    name: "part1_steps",
+    default: 20,
+    min: 0,
+    max: 100_000,
+    about: "how many generations part 1 runs for"
+};
+const PART_2_STEPS : Param = Param {
+    name: "part2_steps",
+    default: 50000000000,
+    min: 0,
+    max: i64::MAX,
+    about: "how many generations part 2 runs for"
+};
+
+// How many generations past part 1 that part 2 waits for the pots to
+// settle. Puzzle inputs take a hundred or so.
+const SETTLE_LIMIT : i64 = 10_000;
+
+#[derive(Debug)]
+#[derive(Hash)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub enum Pot {
+    Empty,
+    Plant
+}
+
+impl From<char> for Pot {
+    fn from(c: char) -> Pot {
+        match c {
+            '.' => Pot::Empty,
+            '#' => Pot::Plant,
+            _   => { panic!("{} is not a Pot", c); }
+        }
+    }
+}           
+    
+
+impl fmt::Display for Pot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Pot::Empty => '.',
+            Pot::Plant => '#'
+        };
+        write!(f, "{}", c)        
+    }
+}
+
+#[derive(Hash)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct State(Vec<Pot>);
+
+impl State {
+    fn process(&self, rules: &HashSet<State>) -> State {
+        let State(v) = self;
+        let mut next : Vec<Pot> = Vec::new();
+        let beg_filler = vec![Pot::Empty; 2];
+        let end_filler = vec![Pot::Empty; 3];
+        let v : Vec<Pot> = beg_filler.iter().chain(v.iter()).chain(end_filler.iter()).cloned().collect();
+        
+        for window in v.windows(5) {
+            let s = State(window.to_vec());
+            next.push( if rules.contains(&s) { Pot::Plant } else { Pot::Empty } );
+        }
+        State(next)
+    }
+
+    fn value(&self, offset: i32) -> i32 {
+        let State(v) = self;
+        let iter = v.iter();
+        let mut sum = 0;
+        for (idx, pot) in (0..).zip(iter) {
+            if *pot == Pot::Plant {
+                let val = idx - offset;
+                sum += val;
+            }
+        }
+        sum
+    }
+}
+
+impl<'a> From<&'a str> for State {
+    fn from(s: &'a str) -> State {
+        let mut state : Vec<Pot> = Vec::new();
+        for c in s.chars() {
+            state.push( match c {
+                '#' => Pot::Plant,
+                '.' => Pot::Empty,
+                _  => { panic!("'{}' is Neither Pot nor empty!", c ); }
+            });
+        }
+        State(state)
+    }
+}
+
+/*
+impl std::hash::Hash for State {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let State(v) = self;
+        v.iter().map(|x| { x.hash(state); });
+    }
+}
+*/
+        
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let State(v) = self;
+        for pot in v.iter() {
+            pot.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Runs the pots forward `steps` generations
+fn run(init_state: &State, rules: &HashSet<State>, steps: usize) -> State {
+    let mut cur_state = init_state.clone();
+    for n in 0..steps {
+        trace!("day_12::generations", "{:02}: {}", n, cur_state);
+        cur_state = cur_state.process(rules);
+    }
+    trace!("day_12::generations", "{:02}: {}", steps, cur_state);
+    cur_state
+}
+
+// Every generation from the first to `steps`, one row each. Pots line up
+// across rows; later generations are longer.
+fn generations(init_state: &State, rules: &HashSet<State>, steps: usize) -> Vec<State> {
+    let mut states = vec![init_state.clone()];
+    for _n in 0..steps {
+        let next = states[states.len() - 1].process(rules);
+        states.push(next);
+    }
+    states
+}
+
+// The generations stacked top to bottom, padded to the widest
+fn diagram(states: &[State], width: usize) -> Frame {
+    Frame::new(Grid::from_fn(width, states.len(), |(x, y)| {
+        let State(ref pots) = states[y];
+        if pots.get(x) == Some(&Pot::Plant) { '#' } else { '.' }
+    })).with_palette(|c| if c == '#' { Kind::Plant } else { Kind::Empty })
+}
+
+/// The initial state, the rules that produce a plant, and how many
+/// generations each part runs for.
+pub struct Pots {
+    init_state: State,
+    rules: HashSet<State>,
+    part1_steps: usize,
+    part2_steps: i64,
+}
+
+/// Part 1's generations stacked top to bottom: a space-time diagram of
+/// the plants spreading.
+pub fn space_time(pots: &Pots) -> Frame {
+    let states = generations(&pots.init_state, &pots.rules, pots.part1_steps);
+    let width = states[states.len() - 1].0.len();
+    diagram(&states, width)
+}
+
+/// Shows part 1's generations appearing one row at a time, then waits for
+/// a key.
+pub fn animate(pots: &Pots, backend: &mut dyn Backend) {
+    let states = generations(&pots.init_state, &pots.rules, pots.part1_steps);
+    let width = states[states.len() - 1].0.len();
+    for n in 0..states.len() {
+        backend.draw(&diagram(&states[..=n], width).with_text(&format!("Generation {}", n)));
+    }
+    backend.wait();
+    backend.finish();
+}
+
+/// The sum of the pot numbers after part 2's generations. When those are
+/// too many to run, the pots have settled into drifting by the same
+/// amount each generation, and the sum is projected from there.
+pub struct Projection {
+    pub steps: i64,
+    /// The generation the drift was found at, the sum then, and how much
+    /// it grows by each generation.
+    pub settled: Option<(i64, i64, i32)>,
+    pub value: i64,
+}
+
+impl fmt::Display for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl Answer for Projection {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        let mut facts = vec![("steps", self.steps.into())];
+        if let Some((step, value, delta)) = self.settled {
+            facts.extend(vec![("loop_step", step.into()), ("loop_value", value.into()), ("delta", delta.into())]);
+        }
+        facts
+    }
+
+    fn explain(&self) -> Vec<String> {
+        match self.settled {
+            Some((step, value, delta)) => vec![
+                format!("from generation {} the sum grows by {} each generation; it was {} then", step, delta, value),
+                format!("{} + ({} - {}) × {} = {}", value, self.steps, step, delta, self.value),
+            ],
+            None => vec![format!("ran all {} generations, which ended before the pots settled", self.steps)]
+        }
+    }
+}
+
+// Runs on from `cur_state`, which is generation `step`, until the total
+// value grows by the same amount twice in a row. Returns that generation,
+// its number and the growth, or None if it takes more than `limit` more
+// generations.
+fn settle(mut cur_state: State, rules: &HashSet<State>, mut step: i64, limit: i64) -> Option<(State, i64, i32)> {
+    let mut history = vec![cur_state.value(3)];
+    for _ in 0..limit {
+        cur_state = cur_state.process(rules);
+        step += 1;
+        history.push(cur_state.value(3));
+        while history.len() > 3 {
+            history.remove(0);
+        }
+        if history.len() == 3 && history[1] - history[0] == history[2] - history[1] {
+            let delta = history[2] - history[1];
+            return Some((cur_state, step, delta));
+        }
+    }
+    None
+}
+
+#[derive(Default)]
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY : u32 = 12;
+
+    const PARAMS : &'static [Param] = &[PART_1_STEPS, PART_2_STEPS];
+
+    type Input = Pots;
+    type Answer1 = i32;
+    type Answer2 = Projection;
+
+    fn parse(&self, input: &str) -> Result<Pots, ParseError> {
+        self.parse_with(input, &Params::default())
+    }
+
+    fn parse_with(&self, input: &str, params: &Params) -> Result<Pots, ParseError> {
+        let init_re = Regex::new(r"initial state: ([#.]+)").unwrap();
+        let rule_re = Regex::new(r"([#.]{5}) => ([#.])").unwrap();
+
+        let mut lines = aoc_common::lines(input);
+
+        // Parse the initial line
+        let init_line = lines.next().ok_or_else(|| {
+            ParseError::at_end(input, "expected the initial state")
+        })?;
+
+        let init_state = if let Some(captures) = init_re.captures(init_line.text) {
+            State::from(&captures[1])
+        }
+        else {
+            return Err(init_line.error("expected a line such as: initial state: #..#.#"));
+        };
+        verbose!("day_12", "initial state: {}", init_state);
+
+        // Skip a line...
+        lines.next();
+
+        let mut rule_set : HashSet<State> = HashSet::new();
+
+        for line in lines {
+            if let Some(captures) = rule_re.captures(line.text) {
+                let rule = State::from(&captures[1]);
+                let value = Pot::from(captures[2].chars().next().unwrap());
+                trace!("day_12::rules", "{} => {}", rule, value);
+                if value == Pot::Plant {
+                    rule_set.insert(rule);
+                }
+            }
+            else {
+                return Err(line.error("expected a rule such as: ...## => #"));
+            }
+        }
+        Ok(Pots {
+            init_state,
+            rules: rule_set,
+            part1_steps: params.value(&PART_1_STEPS).map_err(|e| ParseError::at_end(input, &e))?,
+            part2_steps: params.value(&PART_2_STEPS).map_err(|e| ParseError::at_end(input, &e))?
+        })
+    }
+
+    fn part1(&self, pots: &Pots) -> i32 {
+        // at this point, state starts at -3 * STEPS
+        run(&pots.init_state, &pots.rules, pots.part1_steps).value(3)
+    }
+
+    fn part2(&self, pots: &Pots) -> Projection {
+        // Part 2 can't be brute forced. It's way way too many steps.
+        // We need to detect when we are just shifting to the right and
+        // determine how many more shifts we'll perform
+        let cur_state = run(&pots.init_state, &pots.rules, pots.part1_steps);
+        let (cur_state, step, delta) = settle(cur_state, &pots.rules, pots.part1_steps as i64, SETTLE_LIMIT)
+            .unwrap_or_else(|| panic!("the pots don't settle into a steady drift within {} generations", SETTLE_LIMIT));
+        if pots.part2_steps <= step {
+            // Over before the drift showed up, so it can just be run
+            let steps = usize::try_from(pots.part2_steps).expect("part2_steps is never negative");
+            let value = run(&pots.init_state, &pots.rules, steps).value(3) as i64;
+            return Projection { steps: pots.part2_steps, settled: None, value };
+        }
+        let loop_init_value = cur_state.value(3) as i64;
+        verbose!("day_12", "found a loop at step {}, value = {}, delta = {}",
+                 step, loop_init_value, delta);
+        let steps_left = pots.part2_steps - step;
+        let value = steps_left.checked_mul(delta as i64).and_then(|grown| grown.checked_add(loop_init_value))
+            .unwrap_or_else(|| panic!("the sum of the pots after {} generations is too big for an i64", pots.part2_steps));
+        Projection { steps: pots.part2_steps, settled: Some((step, loop_init_value, delta)), value }
+    }
+
+    // A random row of pots and random rules, tried until part 2's drift
+    // shows up quickly. An empty neighbourhood always stays empty.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let pot = |rng: &mut Rng| if rng.chance(0.4) { '#' } else { '.' };
+        loop {
+            let init : String = (0..std::cmp::max(size, 5)).map(|_| pot(rng)).collect();
+            let rules : Vec<(String, char)> = (0..32).map(|bits : u32| {
+                let pattern = (0..5).rev().map(|i| if bits & (1 << i) != 0 { '#' } else { '.' }).collect();
+                (pattern, if bits == 0 { '.' } else { pot(rng) })
+            }).collect();
+
+            let text = format!("initial state: {}\n\n{}", init, rules.iter().map(|(pattern, result)| {
+                format!("{} => {}\n", pattern, result)
+            }).collect::<String>());
+            let pots = self.parse(&text).expect("generated input doesn't parse");
+            let cur_state = run(&pots.init_state, &pots.rules, pots.part1_steps);
+            if settle(cur_state, &pots.rules, pots.part1_steps as i64, 1000).is_some() {
+                return text;
+            }
+        }
+    }
+}
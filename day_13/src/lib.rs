@@ -0,0 +1,665 @@
+extern crate aoc_common;
+
+use std::fmt;
+
+use aoc_common::lint;
+use aoc_common::{verbose, Answer, Grid, Json, ParseError, Rng, Solution};
+use aoc_common::viz::{Backend, Drawable, Frame, Kind};
+
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Clone)]
+#[derive(Debug)]
+pub enum Track {
+    Vertical,
+    Horizontal,
+    Crossing,
+    LeftTurn,
+    RightTurn,
+}
+
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Clone)]
+#[derive(Debug)]
+pub enum CartDirection {
+    Right,
+    Left,
+    Up,
+    Down,
+    Collision        
+}
+
+impl Track {
+    fn try_from(c: char) -> Option<Self> {
+        match c {
+            '|' | '-' | '+' | '\\' | '/' => Some(Track::from(c)),
+            _ => None
+        }
+    }
+
+    fn to_char(&self) -> char {
+        use Track::*;
+        match self {
+            Vertical => '|',
+            Horizontal => '-',
+            Crossing => '+',
+            LeftTurn => '\\',
+            RightTurn => '/'
+        }                
+    }
+
+    fn do_choice(&mut self, dir: &mut CartDirection) {
+        use Track::*;
+        *self = match &self {
+            LeftTurn => { dir.rotate_left(); Vertical },
+            RightTurn => { dir.rotate_right(); LeftTurn },
+            Vertical => RightTurn,
+            _ => { panic!("Bad track type for 'choice' {:?}", self) }
+        }
+    }    
+}
+
+impl std::convert::From<char> for Track {
+    fn from(c: char) -> Self {
+        use Track::*;
+        match c {
+            '|' => Vertical,
+            '-' => Horizontal,
+            '+' => Crossing,
+            '\\' => LeftTurn,
+            '/' => RightTurn,
+            _ => { panic!("invalid char for Track: {}", c); }
+       }
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_char().fmt(fmt)
+    }
+}
+
+impl<'a> std::convert::From<&'a CartDirection> for Track {
+    fn from(cart: &'a CartDirection) -> Self {
+        use Track::*;
+        use CartDirection::*;
+        match cart {
+            Down | Up => Vertical,
+            Left | Right => Horizontal,
+            Collision => panic!("Not valid source for Track")
+       }
+    }
+}
+
+#[derive(Eq)]
+#[derive(Clone)]
+pub struct Cart((usize,usize), CartDirection, Track);
+
+impl Cart {
+    fn do_move(&mut self) {
+        use CartDirection::*;
+        
+        let Cart((ref mut x, ref mut y), dir, _) = self;
+        match dir {
+            Down  => {*y += 1;},
+            Up    => {*y -= 1;},
+            Right => {*x += 1;},
+            Left  => {*x -= 1;},
+            Collision => {}
+        };
+    }
+
+    fn do_rotate(&mut self, track: &Track) {
+        use Track::*;
+        use CartDirection::*;
+        let Cart(_, ref mut dir, ref mut choice) = self;
+        match track {
+            LeftTurn => {
+                match dir {
+                    Up | Down => { dir.rotate_left(); },
+                    Left | Right => { dir.rotate_right(); },
+                    Collision => {}
+                }
+            },
+            RightTurn => {
+                match dir {
+                    Up | Down => { dir.rotate_right(); },
+                    Left | Right => { dir.rotate_left(); },
+                    Collision => {}
+                }
+            },
+            Crossing => { choice.do_choice(dir); }
+            _ => {}
+        };
+    }
+}
+
+impl std::cmp::PartialOrd for Cart {
+    fn partial_cmp(&self, other: &Cart) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::cmp::PartialEq for Cart {
+    fn eq(&self, other: &Cart) -> bool {
+        let Cart((x0,y0),_,_) = self;
+        let Cart((x1,y1),_,_) = other;
+
+        x0 == x1 && y0 == y1
+    }
+}
+
+impl std::cmp::Ord for Cart {
+    fn cmp(&self, other: &Cart) -> std::cmp::Ordering {
+        let Cart((x0,y0),_,_) = self;
+        let Cart((x1,y1),_,_) = other;
+        if y0 == y1 {
+            x0.cmp(x1)
+        }
+        else {
+            y0.cmp(y1)
+        }
+    }
+}
+
+impl CartDirection {
+    fn try_from(c: char) -> Option<Self> {
+        match c {
+            '>' | '<' | '^' | 'v' => Some(CartDirection::from(c)),
+            _ => None
+        }
+    }
+
+    fn rotate_left(&mut self) {
+        use CartDirection::*;
+        *self = match &self {
+            Down => Right,
+            Up => Left,
+            Right => Up,
+            Left => Down,
+            Collision => Collision                
+        };
+    }
+    fn rotate_right(&mut self) {
+        use CartDirection::*;
+        *self = match &self {
+            Down => Left,
+            Up => Right,
+            Right => Down,
+            Left => Up,
+            Collision => Collision
+        };
+    }
+
+    fn to_char(&self) -> char {
+        use CartDirection::*;
+        match self {
+            Down => 'v',
+            Up => '^',
+            Right => '>',
+            Left => '<',
+            Collision => 'X'
+                
+        }
+    }
+}
+
+impl fmt::Display for CartDirection {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_char().fmt(fmt)
+    }
+}
+
+impl std::convert::From<char> for CartDirection {
+    fn from(c: char) -> Self {
+        use CartDirection::*;
+        match c {
+            '>' => Right,
+            '<' => Left,
+            '^' => Up,
+            'v' => Down,
+            _ => { panic!("invalid char for Cart: {}", c); }                
+        }
+    }
+}
+
+impl CartDirection {
+    // Where a cart at `pos` heading this way goes next, if that's still
+    // on the map
+    fn ahead(&self, (x, y): (usize, usize), grid: &Grid<Option<Track>>) -> Option<(usize, usize)> {
+        use CartDirection::*;
+        let pos = match self {
+            Down  => (x, y + 1),
+            Up    => (x, y.checked_sub(1)?),
+            Right => (x + 1, y),
+            Left  => (x.checked_sub(1)?, y),
+            Collision => (x, y)
+        };
+        if grid.contains(pos) { Some(pos) } else { None }
+    }
+
+    fn index(&self) -> usize {
+        use CartDirection::*;
+        match self {
+            Right => 0,
+            Left => 1,
+            Up => 2,
+            Down => 3,
+            Collision => 4
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        use CartDirection::*;
+        match self {
+            Right => "right",
+            Left => "left",
+            Up => "up",
+            Down => "down",
+            Collision => "nowhere"
+        }
+    }
+}
+
+// Every place a cart could leave the track: a position and the way the
+// cart would be heading, found by following the carts from where they
+// start. At a crossing, any of the three turns might be next.
+fn leaks(mine: &Mine) -> Vec<((usize, usize), CartDirection)> {
+    let grid = &mine.grid;
+    let mut seen = Grid::new(grid.width(), grid.height(), [false; 5]);
+    let mut leaks = Vec::new();
+    let mut queue : Vec<((usize, usize), CartDirection)> = mine.carts.iter().map(|Cart(pos, dir, _)| (*pos, dir.clone())).collect();
+    while let Some((pos, dir)) = queue.pop() {
+        if std::mem::replace(&mut seen[pos][dir.index()], true) {
+            continue;
+        }
+        let track = match &grid[pos] {
+            Some(track) => track,
+            None => continue
+        };
+        let choices : &[Track] = if *track == Track::Crossing { &[Track::LeftTurn, Track::Vertical, Track::RightTurn] } else { &[Track::Vertical] };
+        for choice in choices {
+            let mut cart = Cart(pos, dir.clone(), choice.clone());
+            cart.do_rotate(track);
+            let Cart(_, turned, _) = cart;
+            match turned.ahead(pos, grid) {
+                Some(next) if grid[next].is_some() => queue.push((next, turned)),
+                _ => leaks.push((pos, turned))
+            }
+        }
+    }
+    leaks.sort_by_key(|&((x, y), ref dir)| (y, x, dir.index()));
+    leaks.dedup();
+    leaks
+}
+
+// Reads the map and the carts on it, without checking where they go
+fn read_mine(input: &str) -> Result<Mine, ParseError> {
+    let mut carts : Vec<Cart> = Vec::new();
+    let grid = Grid::parse(input, |pos, c| {
+        if c == ' ' {
+            Ok(None)
+        }
+        else if let Some(track) = Track::try_from(c) {
+            Ok(Some(track))
+        }
+        else if let Some(cart_dir) = CartDirection::try_from(c) {
+            let track = Track::from(&cart_dir);
+            carts.push( Cart( pos, cart_dir, Track::LeftTurn) );
+            Ok(Some(track))
+        }
+        else {
+            Err(format!("expected a track or a cart, found {:?}", c))
+        }
+    })?;
+    Ok(Mine { grid, carts })
+}
+
+fn leak_error(input: &str, (pos, dir): &((usize, usize), CartDirection)) -> ParseError {
+    ParseError::at(input, *pos, &format!("a cart heading {} from here runs off the track", dir.name()))
+}
+
+// Returns the indices of the carts which are colliding
+fn check_collisions(cart_idx: usize,
+                    carts: &[Cart]) -> Option<Vec<usize>> {
+
+    let Cart((x0, y0),_,_) = carts[cart_idx];
+    for (idx, Cart((x,y),dir,_)) in (0..).zip(carts.iter()) {
+        if x0 == *x && y0 == *y
+            && cart_idx != idx
+            && *dir != CartDirection::Collision { 
+                return Some(vec![cart_idx, idx]);
+        }
+    }
+    None
+}
+
+/// The track layout and the carts on it, as read from the input.
+pub struct Mine {
+    grid: Grid<Option<Track>>,
+    carts: Vec<Cart>
+}
+
+/// Gets told about every move of a simulation, so it can be drawn.
+trait View {
+    fn moved(&mut self, _from: (usize,usize), _cart: &Cart, _old_track: Option<&Track>) {}
+    fn tick(&mut self, _step: u32, _carts_left: usize) {}
+}
+
+struct NoView;
+
+impl View for NoView {}
+
+// The crash sites in the order they happened, and the carts that are left
+type Ending = (Vec<(usize,usize)>, Vec<Cart>);
+
+/// The last cart left once the others have crashed, unless they all did.
+pub struct LastCart(Option<(usize, usize)>);
+
+impl fmt::Display for LastCart {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some((x, y)) => write!(fmt, "{},{}", x, y),
+            None => write!(fmt, "none")
+        }
+    }
+}
+
+impl Answer for LastCart {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        match self.0 {
+            Some((x, y)) => vec![("x", x.into()), ("y", y.into())],
+            None => vec![("carts_left", 0.into())]
+        }
+    }
+
+    fn explain(&self) -> Vec<String> {
+        match self.0 {
+            Some((x, y)) => vec![format!("the last cart is at {},{}", x, y)],
+            None => vec!["every cart crashed, leaving none".to_string()]
+        }
+    }
+}
+
+// Runs the carts until at most one is left.
+fn simulate(mine: &Mine, view: &mut dyn View) -> Ending {
+    simulate_within(mine, view, u32::MAX, false).expect("the carts go round forever without crashing")
+}
+
+// Runs the carts until the first crash, which is all part 1 needs even if
+// the carts left would go round forever.
+fn first_crash(mine: &Mine) -> (usize,usize) {
+    let (crashes, _) = simulate_within(mine, &mut NoView, u32::MAX, true).expect("the carts go round forever without crashing");
+    crashes[0]
+}
+
+// Whether two lots of carts are all where they were, heading the same way
+// and due the same turn. Carts only compare their positions otherwise.
+fn same_carts(a: &[Cart], b: &[Cart]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(Cart(pos_a, dir_a, choice_a), Cart(pos_b, dir_b, choice_b))| {
+        pos_a == pos_b && dir_a == dir_b && choice_a == choice_b
+    })
+}
+
+// As simulate, or stopping at the first crash, but gives up after
+// max_steps steps, or once the carts are back the way they were since the
+// last crash, as then they never crash again
+fn simulate_within(mine: &Mine, view: &mut dyn View, max_steps: u32, first_crash: bool) -> Option<Ending> {
+    let grid = &mine.grid;
+    let mut carts = mine.carts.clone();
+    let mut step = 0;
+
+    // Brent's cycle finding: the carts are compared with a copy saved at
+    // ever doubling intervals
+    carts.sort();
+    let mut saved = carts.clone();
+    let (mut power, mut since_saved) = (1u64, 0u64);
+
+    let mut crashes : Vec<(usize,usize)> = Vec::new();
+    'main_loop: loop {
+
+        carts.sort();
+        for idx in 0..carts.len() {
+            let (oldx,oldy) = {let Cart((x,y), _,_) = carts[idx]; (x, y)};
+
+            carts[idx].do_move();
+
+            if let Some(collisions) = check_collisions(idx, &carts) {
+                for i in collisions {
+                    let Cart((x,y),ref mut dir,_) = carts[i];
+                    crashes.push((x,y));
+                    *dir = CartDirection::Collision;
+                }
+            }
+
+            view.moved((oldx, oldy), &carts[idx], grid[(oldx, oldy)].as_ref());
+
+            let (x,y) = {let Cart((x,y), _,_) = carts[idx]; (x, y)};
+            if let Some(Some(track)) = grid.get((x,y)) {
+                carts[idx].do_rotate(track);
+            }
+        }
+        let crashed = carts.len();
+        carts.retain(|Cart((_,_), dir, _)| { *dir != CartDirection::Collision });
+        let crashed = crashed != carts.len();
+
+        view.tick(step, carts.len());
+        step += 1;
+
+        if carts.len() <= 1 || (first_crash && crashed) {
+            break 'main_loop;
+        }
+        if step >= max_steps {
+            return None;
+        }
+
+        carts.sort();
+        since_saved += 1;
+        if crashed || since_saved == power {
+            saved = carts.clone();
+            power = if crashed { 1 } else { power * 2 };
+            since_saved = 0;
+        }
+        else if same_carts(&carts, &saved) {
+            return None;
+        }
+    }
+    Some((crashes, carts))
+}
+
+impl Drawable for Mine {
+    fn frame(&self) -> Frame {
+        let mut cells = Grid::from_fn(self.grid.width(), self.grid.height(), |pos| {
+            self.grid[pos].as_ref().map_or(' ', Track::to_char)
+        });
+        for Cart(pos, dir, _) in self.carts.iter() {
+            cells[*pos] = dir.to_char();
+        }
+        Frame::new(cells).with_palette(|c| {
+            if Track::try_from(c).is_some() {
+                Kind::Track
+            }
+            else if c == 'X' {
+                Kind::Crash
+            }
+            else if CartDirection::try_from(c).is_some() {
+                Kind::Cart
+            }
+            else {
+                Kind::Empty
+            }
+        })
+    }
+}
+
+// Keeps a frame up to date as the carts move, showing it after each step
+struct FrameView<'a> {
+    frame: Frame,
+    backend: &'a mut dyn Backend,
+}
+
+impl<'a> View for FrameView<'a> {
+    fn moved(&mut self, from: (usize,usize), cart: &Cart, old_track: Option<&Track>) {
+        let Cart(pos, ref dir, _) = *cart;
+        if let Some(old_track) = old_track {
+            self.frame.cells[from] = old_track.to_char();
+        }
+        self.frame.cells[pos] = dir.to_char();
+    }
+
+    fn tick(&mut self, step: u32, carts_left: usize) {
+        self.frame.text = vec![format!("Step {}", step),
+                               format!("Carts Left: {}", carts_left)];
+        self.backend.draw(&self.frame);
+    }
+}
+
+/// Shows the carts running around the track until at most one is left,
+/// then waits for a key.
+pub fn animate(mine: &Mine, backend: &mut dyn Backend) {
+    let frame = mine.frame().with_text("Drawing grid...");
+    backend.draw(&frame);
+
+    let mut view = FrameView { frame, backend };
+    simulate(mine, &mut view);
+
+    view.frame.text.push("Finished, press any key to continue...".to_string());
+    view.backend.draw(&view.frame);
+    view.backend.wait();
+    view.backend.finish();
+}
+
+// Lays a rectangular loop of track at random onto the map, unless it would
+// run along other track or put a corner on it. Straight track can only
+// cross straight track at right angles, which makes a crossing.
+fn add_loop(rng: &mut Rng, map: &mut Grid<char>) -> bool {
+    let side = map.width() as i64;
+    let (x0, y0) = (rng.between(0, side - 3) as usize, rng.between(0, side - 3) as usize);
+    let (x1, y1) = (rng.between(x0 as i64 + 2, side - 1) as usize, rng.between(y0 as i64 + 2, side - 1) as usize);
+
+    let mut pieces = Vec::new();
+    for x in x0..=x1 {
+        for &y in [y0, y1].iter() {
+            let c = if x == x0 { if y == y0 { '/' } else { '\\' } }
+                    else if x == x1 { if y == y0 { '\\' } else { '/' } }
+                    else { '-' };
+            pieces.push(((x, y), c));
+        }
+    }
+    for y in y0 + 1..y1 {
+        pieces.push(((x0, y), '|'));
+        pieces.push(((x1, y), '|'));
+    }
+
+    let mut laid = Vec::new();
+    for (pos, c) in pieces {
+        match (map[pos], c) {
+            (' ', c) => laid.push((pos, c)),
+            ('|', '-') | ('-', '|') => laid.push((pos, '+')),
+            _ => return false
+        }
+    }
+    for (pos, c) in laid {
+        map[pos] = c;
+    }
+    true
+}
+
+#[derive(Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY : u32 = 13;
+
+    type Input = Mine;
+    type Answer1 = String;
+    type Answer2 = LastCart;
+
+    fn parse(&self, input: &str) -> Result<Mine, ParseError> {
+        let mine = read_mine(input)?;
+        let carts = &mine.carts;
+        verbose!("day_13", "{} carts found", carts.len());
+        if carts.len() < 2 {
+            return Err(ParseError::at_end(input, &format!("expected at least two carts to crash, found {}", carts.len())));
+        }
+        match leaks(&mine).first() {
+            Some(leak) => Err(leak_error(input, leak)),
+            None => Ok(mine)
+        }
+    }
+
+    // Every character is track or a cart, the map is rectangular, and
+    // there are enough carts to crash
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let mut carts = 0;
+        let mut errors = lint::each_char(input, |_, c| {
+            if CartDirection::try_from(c).is_some() {
+                carts += 1;
+            }
+            if c == ' ' || Track::try_from(c).is_some() || CartDirection::try_from(c).is_some() {
+                Ok(())
+            }
+            else {
+                Err(format!("expected a track or a cart, found {:?}", c))
+            }
+        });
+        errors.extend(lint::rectangular(input));
+        if carts < 2 {
+            errors.push(ParseError::at_end(input, &format!("expected at least two carts to crash, found {}", carts)));
+        }
+        // The track can only be followed once every character reads
+        if errors.is_empty() {
+            if let Ok(mine) = read_mine(input) {
+                errors.extend(leaks(&mine).iter().map(|leak| leak_error(input, leak)));
+            }
+        }
+        errors
+    }
+
+    fn part1(&self, mine: &Mine) -> String {
+        let (x,y) = first_crash(mine);
+        format!("{},{}", x, y)
+    }
+
+    fn part2(&self, mine: &Mine) -> LastCart {
+        let (_, carts) = simulate(mine, &mut NoView);
+        LastCart(carts.first().map(|Cart(pos, _, _)| *pos))
+    }
+
+    // Loops of track with an odd number of carts on their straight pieces,
+    // so crashes always leave one. Layouts are thrown away until the carts
+    // get down to one in a reasonable number of steps.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let side = std::cmp::max(size, 8);
+        loop {
+            let mut map = Grid::new(side, side, ' ');
+            for _ in 0..side {
+                add_loop(rng, &mut map);
+            }
+
+            let mut straight : Vec<(usize,usize)> = map.positions()
+                .filter(|&pos| map[pos] == '-' || map[pos] == '|')
+                .collect();
+            let carts = 2 * rng.between(1, std::cmp::max(1, side as i64 / 6)) as usize + 1;
+            if straight.len() < carts {
+                continue;
+            }
+            rng.shuffle(&mut straight);
+            for &pos in straight.iter().take(carts) {
+                map[pos] = match (map[pos], rng.chance(0.5)) {
+                    ('-', true) => '>',
+                    ('-', false) => '<',
+                    (_, true) => 'v',
+                    (_, false) => '^'
+                };
+            }
+
+            let text = map.to_string();
+            let mine = self.parse(&text).expect("generated a map that doesn't parse");
+            if simulate_within(&mine, &mut NoView, 10_000, false).is_some() {
+                return text;
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+extern crate aoc_common;
+extern crate regex;
+
+use aoc_common::{InputShape, ParseError, Rng, Solution};
+use regex::Regex;
+
+// The most recipes either part makes before giving up. Puzzle inputs
+// need a few tens of millions.
+const MAX_RECIPES : usize = 100_000_000;
+
+// Makes new recipes until `done` says to stop, and returns the scores
+fn make_recipes<F: FnMut(&[usize]) -> bool>(mut done: F) -> Vec<usize> {
+    let mut scores : Vec<usize> = vec![3, 7];
+    let mut cur_scores : Vec<usize> = vec![0, 1];
+
+    while !done(&scores) {
+        let mut new_scores : Vec<usize> = cur_scores.iter().fold(0, |sum, idx| { sum + scores[*idx] }).to_string().chars().map(|d| { d.to_digit(10).unwrap() as usize }).collect();
+        scores.append(&mut new_scores);
+        cur_scores = cur_scores.into_iter().map(|x| { (x + scores[x] + 1) % scores.len() }).collect();
+    }
+    scores
+}
+
+#[derive(Default)]
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY : u32 = 14;
+    const INPUT : InputShape = InputShape::Values(&["recipe_count"]);
+
+    type Input = usize;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<usize, ParseError> {
+        let line = aoc_common::lines(input).next().ok_or_else(|| {
+            ParseError::at_end(input, "expected <recipe_count>")
+        })?;
+        let count = line.parse::<usize>(line.text.trim(), "an unsigned int for recipe_count")?;
+        if count > MAX_RECIPES - 10 {
+            return Err(line.error(&format!("expected a recipe_count of at most {}", MAX_RECIPES - 10)));
+        }
+        Ok(count)
+    }
+
+    fn part1(&self, &input: &usize) -> String {
+        let scores = make_recipes(|scores| scores.len() >= input + 10);
+        scores[input..(input + 10)].iter().map(|d| d.to_string()).collect()
+    }
+
+    fn part2(&self, &input: &usize) -> usize {
+        let input_str = input.to_string();
+
+        let re = Regex::new(&format!("^(.*){}", input_str)).unwrap();
+        let mut part_2 : Option<usize> = None;
+        let mut scores_str = String::new();
+        let mut num_removed = 0;
+        let mut num_seen = 0;
+
+        make_recipes(|scores| {
+            for d in scores[num_seen..].iter() {
+                scores_str.push_str(&d.to_string());
+            }
+            num_seen = scores.len();
+
+            if let Some(captures) = re.captures(&scores_str) {
+                part_2 = Some(captures[1].len() + num_removed);
+            }
+            else {
+                while scores_str.len() > input_str.len() {
+                    scores_str.remove(0);
+                    num_removed += 1;
+                }
+            }
+            part_2.is_some() || scores.len() >= MAX_RECIPES
+        });
+        part_2.unwrap_or_else(|| panic!("{} doesn't turn up in the first {} recipes", input_str, MAX_RECIPES))
+    }
+
+    // Counts of up to five digits, which part 2 finds quickly
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        rng.between(1, std::cmp::min(100 * std::cmp::max(size, 1), 99999) as i64).to_string()
+    }
+}
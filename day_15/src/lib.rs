@@ -0,0 +1,821 @@
+extern crate aoc_common;
+extern crate priority_queue;
+
+use std::collections::HashSet;
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::fmt;
+
+
+macro_rules! displayln {
+    ($display:expr, $str:expr) => ({
+       $display.println($str)
+    });
+    ($display:expr, $fmt:expr, $($arg:tt)+) => ({
+        $display.println(&format!($fmt, $($arg)+))
+    });
+}
+
+
+use aoc_common::lint;
+use aoc_common::{trace, verbose, Answer, Grid, Json, Param, Params, ParseError, Rng, Solution};
+use aoc_common::viz::{Backend, Drawable, Frame, Headless, Kind};
+use priority_queue::PriorityQueue;
+
+
+#[derive(PartialEq)]
+#[derive(Clone)]
+#[derive(Copy)]
+enum UnitType {
+    Goblin,
+    Elf
+}
+
+impl UnitType {
+    fn opposite(&self) -> UnitType {
+        match self {
+            UnitType::Goblin => UnitType::Elf,
+            UnitType::Elf => UnitType::Goblin
+        }
+    }
+}
+
+impl<'a> std::convert::From<&'a UnitType> for char {
+    fn from(unit: &'a UnitType) -> char {
+        use UnitType::*;
+        match unit {
+            Goblin => 'G',
+            Elf => 'E'
+        }
+    }
+}
+
+const STARTING_HP : Param = Param {
+    name: "hit_points",
+    default: 200,
+    min: 1,
+    max: 10_000,
+    about: "the hit points every unit starts with"
+};
+const ATTACK_POW : Param = Param {
+    name: "attack",
+    default: 3,
+    min: 1,
+    max: 10_000,
+    about: "the attack power of goblins, and of elves before part 2 raises it"
+};
+
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[derive(Clone)]
+#[derive(Copy)]
+struct Point(u32,u32);
+
+impl Point {
+    fn pos(&self) -> (usize, usize) {
+        (self.0 as usize, self.1 as usize)
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other : &Point) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Point {
+    fn cmp(&self, other : &Point) -> Ordering {
+        match self.1.cmp(&other.1) {
+            Ordering::Equal => self.0.cmp(&other.0),
+            ordering => ordering
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Unit {
+    unit_type: UnitType,
+    coords: Point,
+    hit_points: u32
+}
+
+impl Unit {
+    fn new(unit_type: UnitType, point: Point, hit_points: u32) -> Self {
+        Unit {
+            unit_type,
+            coords: point,
+            hit_points
+        }
+    }
+}
+
+impl std::convert::From<&Unit> for char {
+    fn from(unit: &Unit) -> Self {
+        char::from(&unit.unit_type)
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}({})", char::from(self), self.hit_points)
+    }
+}
+
+#[derive(Clone)]
+enum Cell {
+    Wall,
+    Empty,
+    Occupied(Unit),
+}
+
+impl Cell {
+    fn new(c: char, p: Point, hit_points: u32) -> Result<Cell, String> {
+        use Cell::*;
+        match c {
+            '#' => Ok(Wall),
+            '.' => Ok(Empty),
+            'G' => Ok(Occupied(Unit::new(UnitType::Goblin, p, hit_points))),
+            'E' => Ok(Occupied(Unit::new(UnitType::Elf, p, hit_points))),
+            _ => Err(format!("expected one of #.GE, found {:?}", c))
+        }
+    }
+}
+
+impl std::convert::From<&Cell> for char {
+    fn from(cell: &Cell) -> Self {
+        use Cell::*;
+        match cell {
+            Wall => '#',
+            Empty => '.',
+            Occupied(ref unit) => char::from(unit)
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        char::from(self).fmt(fmt)
+    }
+}
+
+/// A unit killed in battle: in which round, whether it was an elf (`E`) or
+/// a goblin (`G`), and where it fell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Casualty {
+    pub round: u32,
+    pub unit: char,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl fmt::Display for Casualty {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = if self.unit == 'E' { "elf" } else { "goblin" };
+        write!(fmt, "round {}: the {} at {},{} died", self.round, unit, self.x, self.y)
+    }
+}
+
+#[derive(Clone)]
+pub struct Board {
+    elf_attack_pow: u32,
+    goblin_attack_pow: u32,
+    num_rounds: u32,
+    cells: Grid<Cell>,
+    casualties: Vec<Casualty>
+}
+
+impl Board {
+    fn new(cells: Grid<Cell>, attack_pow: u32) -> Board {
+        assert!(cells.height() > 0);
+
+        Board { elf_attack_pow: attack_pow,
+                goblin_attack_pow: attack_pow,
+                num_rounds: 0,
+                cells,
+                casualties: Vec::new() }
+    }
+
+    fn complete_round (&mut self) { self.num_rounds += 1; }
+
+    fn set_attack_pow(&mut self, unit_type: UnitType, attack_pow: u32) {
+        let dest = match unit_type {
+            UnitType::Elf => &mut self.elf_attack_pow,
+            UnitType::Goblin => &mut self.goblin_attack_pow
+        };
+        *dest = attack_pow;
+    }
+
+    fn get_attack_pow(&self, unit_type: UnitType) -> u32 {
+        match unit_type {
+            UnitType::Elf => self.elf_attack_pow,
+            UnitType::Goblin => self.goblin_attack_pow
+        }
+    }
+
+    fn parse(input: &str, hit_points: u32, attack_pow: u32) -> Result<Board, ParseError> {
+        let cells = Grid::parse(input, |(x, y), c| {
+            Cell::new(c, Point(x as u32, y as u32), hit_points)
+        })?;
+        if cells.height() == 0 {
+            return Err(ParseError::at_end(input, "expected a map of the cave"));
+        }
+        if let Some(pos) = kept_apart(&cells) {
+            return Err(ParseError::at(input, pos, "no elf can ever reach a goblin, so the fight could never end"));
+        }
+        Ok(Board::new(cells, attack_pow))
+    }
+
+    fn at(&self, point: Point) -> &Cell {
+        &self.cells[point.pos()]
+    }
+
+    fn at_mut(&mut self, point: Point) -> &mut Cell {
+        &mut self.cells[point.pos()]
+    }
+
+    fn units(&self) -> impl Iterator<Item = &Unit> {
+        self.cells.iter().filter_map(|(_, cell)| {
+            match cell {
+                Cell::Occupied(ref unit) => Some(unit),
+                _ => None
+            }
+        })
+    }
+
+    fn units_of_type(&self, unit_type: UnitType) -> impl Iterator<Item = &Unit> {
+	self.units().filter(move |unit| { unit.unit_type == unit_type })
+    }
+
+    fn units_for_row(&self, row: usize) -> impl Iterator<Item = &Unit> {
+        self.cells.row(row).iter().filter_map(|cell| {
+            if let Cell::Occupied(u) = cell {
+                Some(u)
+            }
+            else {
+                None
+            }
+        })
+    }
+
+    fn is_over(&self) -> bool {
+        let mut some_elves = false;
+        let mut some_goblins = false;
+        for unit in self.units() {
+            match unit.unit_type {
+                UnitType::Elf => {some_elves = true;},
+                UnitType::Goblin => {some_goblins = true;}
+            }
+            if some_elves && some_goblins {
+                return false;
+            }
+        }
+        true
+    }
+
+    // In reading order, which is also the order ties are broken in
+    fn neighbors(&self, point: &Point) -> impl Iterator<Item = Point> {
+        self.cells.neighbors4(point.pos()).map(|(x, y)| Point(x as u32, y as u32))
+    }
+
+    fn move_cell(&mut self, from: Point, to: Point) {
+        let moved = std::mem::replace(self.at_mut(from), Cell::Empty);
+        *self.at_mut(to) = moved;
+    }
+
+
+    fn perform_action(&mut self, action: Action) -> Option<Point> {
+        match action {
+            Action::MoveTo(from,to) => {
+                if let Cell::Empty = self.at(to) { /* Unoccupied, OK */ }
+                else {
+                     panic!("Attempting to move to occupied ({},{})!", to.0, to.1);
+                }
+                if let Cell::Occupied(ref mut unit) = self.at_mut(from) {
+                    unit.coords = to
+                }
+                else {
+                     panic!("Attempting to move from non-occupied ({},{})!", from.0, from.1);
+                }
+
+                self.move_cell(from, to);
+                return Some(to);
+            },
+            Action::Attack(from, target) => {
+                let attack_pow : u32;
+                if let Cell::Occupied(ref from) = self.at(from) { attack_pow = self.get_attack_pow(from.unit_type); }
+                else {
+                    panic!("Attempting to use an unoccupied cell to attack ({},{})!", from.0, from.1);
+                }
+                let to = self.at_mut(target);
+                let mut dead = None;
+                if let Cell::Occupied(ref mut to) = to {
+                    if to.hit_points > attack_pow {
+                        to.hit_points -= attack_pow;
+                    }
+                    else {
+                        // They ded
+                        to.hit_points = 0;
+                        dead = Some(char::from(&*to));
+                    }
+                }
+                else {
+                    panic!("Attempting to attack an unoccupied cell!");
+                }
+
+                if let Some(unit) = dead {
+                    let mut empty = Cell::Empty;
+                    std::mem::swap(to, &mut empty);
+                    // The round under way, which may be cut short
+                    self.casualties.push(Casualty { round: self.num_rounds + 1, unit, x: target.0, y: target.1 });
+                }
+            }
+            Action::None => {}
+        }
+        None
+    }
+}
+
+
+// Game Rules
+
+enum Action {
+    None,
+    MoveTo(Point,Point),
+    Attack(Point,Point)
+}
+
+impl Unit {
+    fn targets<'a>(&self, board: &'a Board) -> impl Iterator<Item = &'a Self> {
+        board.units_of_type(self.opposite_type())
+    }
+
+    fn opposite_type(&self) -> UnitType {
+        self.unit_type.opposite()
+    }
+
+    fn in_range<'a>(&self, board: &'a Board) -> impl Iterator<Item = Point> + 'a {
+        self.targets(board).flat_map(move |unit| {
+            trace!("day_15::targets", "neighbors of {},{}", unit.coords.0, unit.coords.1);
+            board.neighbors(&unit.coords).filter(move |point| {
+                trace!("day_15::targets", "{},{}", point.0, point.1);
+                matches!(board.at(*point), Cell::Empty)
+            })
+        })
+    }
+
+    fn decide_attack(&self, board: &Board) -> Option<Action> {
+        let mut lowest_hp : Option<u32> = None;
+        let mut action : Option<Action> = None;
+
+        for point in board.neighbors(&self.coords) {
+            if let Cell::Occupied(ref unit) = board.at(point) {
+                if unit.unit_type == self.opposite_type() {
+                    if lowest_hp.is_some() {
+                        if lowest_hp.expect("??") > unit.hit_points {
+                            lowest_hp = Some(unit.hit_points);
+                            action = Some(Action::Attack(self.coords, point));
+                        }
+                    }
+                    else {
+                        lowest_hp = Some(unit.hit_points);
+                        action = Some(Action::Attack(self.coords, point));
+                    }
+                }
+            }
+        }
+        action
+    }
+
+    fn decide_move(&self, board: &Board) -> Action {
+        // Check for attack
+        if self.decide_attack(board).is_some() {
+            return Action::None;
+        }
+        // Otherwise determine target cell and attempt to move towards it
+        #[derive(Eq)]
+        #[derive(PartialEq)]
+        #[derive(Hash)]
+        struct Weight(u32,Point);
+        impl Ord for Weight {
+            fn cmp(&self, other: &Weight) -> Ordering {
+                match self.0.cmp(&other.0) {
+                    Ordering::Equal => other.1.cmp(&self.1),
+                    Ordering::Less => Ordering::Greater,
+                    Ordering::Greater => Ordering::Less
+                }
+            }
+        }
+
+        impl PartialOrd for Weight {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        let mut ancestor : HashMap<Point,Point> = HashMap::new();
+
+        let move_targets : HashSet<Point> = self.in_range(board).collect();
+        let possible_moves : HashSet<Point> = board.neighbors(&self.coords).collect();
+
+        let mut active_targets : PriorityQueue<Point,Weight> = PriorityQueue::new();
+
+        if move_targets.is_empty() {
+            trace!("day_15::targets", "no targets for {},{}", self.coords.0, self.coords.1);
+            return Action::None
+        }
+        trace!("day_15::targets", "targets for {},{}: {}", self.coords.0, self.coords.1,
+               move_targets.iter().map(|target| format!("{},{}", target.0, target.1)).collect::<Vec<String>>().join(" "));
+
+        // Currently, this uses flood-fill.. it should use A*
+        active_targets.push(self.coords, Weight(0, self.coords));
+        while let Some((next, weight)) = active_targets.pop() {
+            trace!("day_15::search", "looking at {},{} ({})", (weight.1).0, (weight.1).1, weight.0);
+            if move_targets.contains(&next) {
+                // Reached one of the targets
+                // need the first move on the way to the target
+                trace!("day_15::search", "headed for {},{} ({})", (weight.1).0, (weight.1).1, weight.0);
+                let mut the_move = next;
+                while !possible_moves.contains(&the_move) {
+                    match ancestor.get(&the_move) {
+                        None => panic!("Ancestor not found!"),
+                        Some(previous_move) => {the_move = *previous_move;}
+                    }
+                }
+                return Action::MoveTo(self.coords, the_move);
+            }
+            else {
+                // push the neighbors that we haven't visited,
+                // keeping track of how we got there
+                let empty_neighbors = board.neighbors(&next).filter(|point| {
+                    matches!(board.at(*point), Cell::Empty)
+                });
+                for neighbor in empty_neighbors {
+                    if let std::collections::hash_map::Entry::Vacant(entry) = ancestor.entry(neighbor) {
+                        trace!("day_15::search", "pushing {},{} ({})", neighbor.0, neighbor.1, weight.0 + 1);
+                        entry.insert(next);
+                        let result = active_targets.push(neighbor,
+                                                         Weight(weight.0 + 1, neighbor));
+                        if let Some(weight) = result {
+                            panic!("Duplicate priorities: {},{}({})",
+                                   (weight.1).0, (weight.1).1, weight.0);
+                        }
+                    }
+                }
+            }
+        }
+        Action::None
+    }
+
+}
+
+/// Where games are drawn as they're played. Each caller owns its own, so
+/// several games can run side by side as long as at most one of them is
+/// drawing.
+pub struct Display {
+    backend: Box<dyn Backend>,
+    // The last board drawn, with the lines printed under it since
+    frame: Option<Frame>,
+    headless: bool
+}
+
+impl Display {
+    pub fn new(backend: Box<dyn Backend>) -> Self {
+        Display {
+            backend,
+            frame: None,
+            headless: false
+        }
+    }
+
+    // A display that draws nothing, used when solving without a terminal
+    pub fn headless() -> Self {
+        Display {
+            headless: true,
+            ..Display::new(Box::new(Headless))
+        }
+    }
+
+    fn draw(&mut self, board: &Board) {
+        if self.headless { return; }
+        let frame = board.frame();
+        self.backend.draw(&frame);
+        self.frame = Some(frame);
+    }
+
+    pub fn println(&mut self, s: &str) {
+        if self.headless { return; }
+        if let Some(ref mut frame) = self.frame {
+            frame.text.push(s.to_string());
+            self.backend.draw(frame);
+        }
+    }
+
+    pub fn wait_key(&mut self) {
+        self.backend.wait();
+    }
+
+    /// Waits for the viewer to press a key and then tears down the terminal.
+    pub fn finish(&mut self) {
+        if self.headless { return; }
+        displayln!(self, "Finished, press [enter] to exit...");
+        self.backend.wait();
+        self.backend.finish();
+    }
+}
+
+impl Drawable for Board {
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::new(Grid::from_fn(self.cells.width(), self.cells.height(), |pos| {
+            char::from(&self.cells[pos])
+        })).with_palette(|c| match c {
+            '#' => Kind::Wall,
+            'E' => Kind::Elf,
+            'G' => Kind::Goblin,
+            _ => Kind::Empty
+        });
+        frame.side = (0..self.cells.height()).map(|row| {
+            self.units_for_row(row).map(|unit| {
+                format!("{}", unit) }
+            ).collect::<Vec<String>>().join(",")
+        }).collect();
+        frame
+    }
+}
+
+
+// The first elf, when no elf can get to any goblin even once everyone in
+// the way has died
+fn kept_apart(cells: &Grid<Cell>) -> Option<(usize, usize)> {
+    let unit_type = |cell: &Cell| match cell {
+        Cell::Occupied(unit) => Some(unit.unit_type),
+        _ => None
+    };
+    let mut reached = Grid::new(cells.width(), cells.height(), false);
+    for (start, _) in cells.iter().filter(|(_, cell)| unit_type(cell).is_some()) {
+        if reached[start] {
+            continue;
+        }
+        // Flood the open squares this unit can get to
+        let (mut elves, mut goblins) = (false, false);
+        reached[start] = true;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            match unit_type(&cells[pos]) {
+                Some(UnitType::Elf) => elves = true,
+                Some(UnitType::Goblin) => goblins = true,
+                None => {}
+            }
+            for next in cells.neighbors4(pos) {
+                if !reached[next] && !matches!(cells[next], Cell::Wall) {
+                    reached[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        if elves && goblins {
+            return None;
+        }
+    }
+    cells.iter().find(|(_, cell)| unit_type(cell) == Some(UnitType::Elf)).map(|(pos, _)| pos)
+}
+
+fn run_game(mut game_board: Board, d: &mut Display) -> Board {
+    d.draw(&game_board);
+
+    loop {
+        // Next board state
+        let unit_coords = game_board.units().map(|unit| {
+            unit.coords
+        }).collect::<Vec<Point>>();
+
+        let mut round_complete = true;
+        let mut acted = false;
+	for unit_coord in unit_coords {
+            // A unit with no one left to fight ends combat mid-round
+            if let Cell::Occupied(_) = game_board.at(unit_coord) {
+                if game_board.is_over() {
+                    round_complete = false;
+                    break;
+                }
+            }
+
+            // Move phase
+            trace!("day_15::moves", "deciding a move for {},{}", unit_coord.0, unit_coord.1);
+            let action = {
+                let cell = game_board.at(unit_coord);
+                if let Cell::Occupied(unit) = cell {
+                    unit.decide_move(&game_board)
+                }
+                else {
+                    trace!("day_15::moves", "no one left at {},{} to move", unit_coord.0, unit_coord.1);
+                    Action::None
+                }
+            };
+
+            // Perform move
+            let new_pos = game_board.perform_action(action);
+            acted |= new_pos.is_some();
+
+            // Attack phase
+            let attack_pos = new_pos.unwrap_or(unit_coord);
+            trace!("day_15::moves", "attacking from {},{}", attack_pos.0, attack_pos.1);
+            let cell = game_board.at(attack_pos);
+
+            if let Cell::Occupied(unit) = cell {
+                if let Some(attack) = unit.decide_attack(&game_board) {
+                    game_board.perform_action(attack);
+                    acted = true;
+                }
+            }
+            else {
+                trace!("day_15::moves", "no one at {},{} to attack", attack_pos.0, attack_pos.1);
+            }
+	}
+        d.draw(&game_board);
+        if round_complete {
+            game_board.complete_round ();
+            verbose!("day_15", "round {} done: {} elves, {} goblins", game_board.num_rounds,
+                     game_board.units_of_type(UnitType::Elf).count(),
+                     game_board.units_of_type(UnitType::Goblin).count());
+        }
+        if game_board.is_over() {
+            break;
+        }
+        // The next round would go just the same, and so on forever
+        if !acted {
+            panic!("no one could move or attack in round {}, so the fight never ends", game_board.num_rounds);
+        }
+    }
+    game_board
+}
+
+/// How a game ended. The answer is the number of full rounds times the
+/// hit points left.
+pub struct Outcome {
+    pub rounds: u32,
+    pub hit_points: u32,
+    pub elf_attack: u32,
+    /// Everyone who died, in the order they did.
+    pub casualties: Vec<Casualty>,
+}
+
+impl Outcome {
+    fn new(game_board: &Board) -> Outcome {
+        let hit_points = game_board.units().fold(0, {
+            |acc, unit|
+            acc + unit.hit_points
+        });
+        Outcome { rounds: game_board.num_rounds,
+                  hit_points,
+                  elf_attack: game_board.elf_attack_pow,
+                  casualties: game_board.casualties.clone() }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.rounds * self.hit_points)
+    }
+}
+
+impl Answer for Outcome {
+    fn facts(&self) -> Vec<(&'static str, Json)> {
+        vec![("rounds", self.rounds.into()),
+             ("hit_points", self.hit_points.into()),
+             ("elf_attack", self.elf_attack.into()),
+             ("casualties", Json::Array(self.casualties.iter().map(|casualty| Json::object(vec![
+                 ("round", casualty.round.into()),
+                 ("unit", casualty.unit.to_string().into()),
+                 ("x", casualty.x.into()),
+                 ("y", casualty.y.into()),
+             ])).collect()))]
+    }
+
+    fn explain(&self) -> Vec<String> {
+        let mut lines = vec![format!("elves attack with {}", self.elf_attack)];
+        lines.extend(self.casualties.iter().map(|casualty| casualty.to_string()));
+        lines.push(format!("{} full rounds × {} hit points left = {}", self.rounds, self.hit_points, self));
+        lines
+    }
+}
+
+/// Part 1: plays out the battle as given.
+pub fn fight(starting_board: &Board, d: &mut Display) -> Outcome {
+    let game_board = run_game(starting_board.clone(), d);
+    let outcome = Outcome::new(&game_board);
+    displayln!(d, "[Part 1] Outcome: {} + {} = {}",
+               outcome.rounds, outcome.hit_points, outcome);
+    d.wait_key();
+    outcome
+}
+
+/// Part 2: the outcome with the weakest elves that all survive.
+pub fn save_the_elves(starting_board: &Board, d: &mut Display) -> Outcome {
+    let mut elf_attack = starting_board.get_attack_pow(UnitType::Elf);
+    let num_starting_elves = starting_board.units_of_type(UnitType::Elf).count();
+    // Past this every hit kills, so more attack power changes nothing
+    let most_goblin_hp = starting_board.units_of_type(UnitType::Goblin).map(|unit| unit.hit_points).max().unwrap_or(0);
+    loop {
+        elf_attack += 1;
+        let mut game_board = starting_board.clone();
+        game_board.set_attack_pow(UnitType::Elf, elf_attack);
+        game_board = run_game(game_board, d);
+        let num_elves = game_board.units_of_type(UnitType::Elf).count();
+        verbose!("day_15", "elf attack {}: {} of {} elves survive", elf_attack, num_elves, num_starting_elves);
+        if num_elves == num_starting_elves {
+            let outcome = Outcome::new(&game_board);
+            displayln!(d, "[Part 2] Elf Attack = {}, Outcome: {} + {} = {}",
+                       elf_attack, outcome.rounds, outcome.hit_points, outcome);
+            d.wait_key();
+            return outcome;
+        }
+        if elf_attack >= most_goblin_hp {
+            panic!("no attack power saves every elf: at {} every hit already kills a goblin", elf_attack);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY : u32 = 15;
+    const PARAMS : &'static [Param] = &[STARTING_HP, ATTACK_POW];
+
+    type Input = Board;
+    type Answer1 = Outcome;
+    type Answer2 = Outcome;
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        self.parse_with(input, &Params::default())
+    }
+
+    fn parse_with(&self, input: &str, params: &Params) -> Result<Board, ParseError> {
+        let param = |param| params.value(param).map_err(|e| ParseError::at_end(input, &e));
+        Board::parse(input, param(&STARTING_HP)?, param(&ATTACK_POW)?)
+    }
+
+    // Every square is one the cave can have, the cave is rectangular with
+    // walls all round, and there's a fight to be had
+    fn lint(&self, input: &str) -> Vec<ParseError> {
+        let (mut elves, mut goblins) = (0, 0);
+        let mut errors = lint::each_char(input, |_, c| {
+            match c {
+                'E' => elves += 1,
+                'G' => goblins += 1,
+                _ => {}
+            }
+            Cell::new(c, Point(0, 0), STARTING_HP.default as u32).map(|_| ())
+        });
+        errors.extend(lint::rectangular(input));
+        errors.extend(lint::enclosed(input, '#'));
+        if elves == 0 || goblins == 0 {
+            errors.push(ParseError::at_end(input, &format!("expected elves and goblins, found {} and {}", elves, goblins)));
+        }
+        // Whether they can reach each other only shows once the map reads
+        if errors.is_empty() {
+            errors.extend(self.parse(input).err());
+        }
+        errors
+    }
+
+    fn part1(&self, starting_board: &Board) -> Outcome {
+        fight(starting_board, &mut Display::headless())
+    }
+
+    fn part2(&self, starting_board: &Board) -> Outcome {
+        save_the_elves(starting_board, &mut Display::headless())
+    }
+
+    // A cave dug by a random walk from the middle, so every open square
+    // can reach every other, with elves and goblins dropped into it. At
+    // most 16 goblins, so elves with enough attack power lose nobody.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let side = std::cmp::max(size, 7);
+        let mut cave = Grid::new(side, side, '#');
+        let mut open = Vec::new();
+        let (mut x, mut y) = (side / 2, side / 2);
+        for _ in 0..side * side {
+            if cave[(x,y)] == '#' {
+                cave[(x,y)] = '.';
+                open.push((x,y));
+            }
+            match rng.below(4) {
+                0 if x > 1 => x -= 1,
+                1 if x < side - 2 => x += 1,
+                2 if y > 1 => y -= 1,
+                3 if y < side - 2 => y += 1,
+                _ => {}
+            }
+        }
+
+        rng.shuffle(&mut open);
+        let units = std::cmp::min(std::cmp::max(side / 3, 2), open.len());
+        let goblins = std::cmp::min(units / 2, 16);
+        for (i, &pos) in open.iter().take(units).enumerate() {
+            cave[pos] = if i < goblins { 'G' } else { 'E' };
+        }
+        cave.to_string()
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+extern crate aoc;
+#[macro_use]
+extern crate libfuzzer_sys;
+
+fuzz_target!(|data: &[u8]| {
+    aoc::fuzz::parse(aoc::find(1).unwrap(), data);
+});